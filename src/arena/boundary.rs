@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+
+use crate::{
+    boid::Boid,
+    health::{self, DamageKind},
+    player::Player,
+    velocity::{self, Velocity},
+    GameState,
+};
+
+use super::Arena;
+
+/// How far inside the new edge a player left outside by a reshape is put.
+const RESHAPE_MARGIN: f32 = 20.0;
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoundarySettings>();
+        app.add_systems(
+            PostUpdate,
+            (boids, player)
+                .after(velocity::update)
+                .run_if(not(in_state(GameState::Paused))),
+        );
+        app.add_systems(Update, hazard.run_if(in_state(GameState::Playing)));
+        app.add_systems(
+            PreUpdate,
+            enclose
+                .after(super::reshape)
+                .run_if(on_event::<super::Event>()),
+        );
+    }
+}
+
+/// How the arena edge treats something trying to leave. A torus arena always wraps instead.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Boundary {
+    Steer {
+        margin: f32,
+        force: f32,
+    },
+    Bounce,
+    Wall,
+    /// Hurts anything outside every `interval` seconds while pulling it back with `force`.
+    Damage {
+        amount: u32,
+        interval: f32,
+        force: f32,
+    },
+}
+
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct BoundarySettings {
+    pub boids: Boundary,
+    pub player: Boundary,
+}
+
+impl Default for BoundarySettings {
+    fn default() -> Self {
+        Self {
+            boids: Boundary::Steer {
+                margin: 50.0,
+                force: 1200.0,
+            },
+            player: Boundary::Steer {
+                margin: 0.0,
+                force: 900.0,
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+struct Exposure(f32);
+
+fn contain(arena: &Arena, boundary: Boundary, transform: &mut Transform, vel: &mut Vec2, dt: f32) {
+    let pos = transform.translation.xy();
+    if arena.wraps() {
+        transform.translation = arena.wrap(pos).extend(transform.translation.z);
+        return;
+    }
+
+    let depth = arena.depth(pos);
+    let normal = arena.normal(pos);
+    match boundary {
+        Boundary::Steer { margin, force } => {
+            if depth < margin {
+                let strength = ((margin - depth) / margin.max(f32::EPSILON)).min(1.0);
+                *vel += normal * force * strength * dt;
+            }
+        }
+        Boundary::Bounce => {
+            if depth < 0.0 {
+                transform.translation = arena.edge_point(pos).extend(transform.translation.z);
+                if vel.dot(normal) < 0.0 {
+                    *vel -= 2.0 * vel.dot(normal) * normal;
+                }
+            }
+        }
+        Boundary::Wall => {
+            if depth < 0.0 {
+                transform.translation = arena.edge_point(pos).extend(transform.translation.z);
+                *vel -= vel.dot(normal).min(0.0) * normal;
+            }
+        }
+        Boundary::Damage { force, .. } => {
+            if depth < 0.0 {
+                *vel += normal * force * dt;
+            }
+        }
+    }
+}
+
+fn boids(
+    settings: Res<BoundarySettings>,
+    arena: Res<Arena>,
+    mut boids: Query<(&mut Transform, &mut Velocity), With<Boid>>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut vel) in &mut boids {
+        contain(
+            &arena,
+            settings.boids,
+            &mut transform,
+            &mut vel.0,
+            time.delta_seconds(),
+        );
+    }
+}
+
+fn player(
+    settings: Res<BoundarySettings>,
+    arena: Res<Arena>,
+    mut player: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut vel) in &mut player {
+        contain(
+            &arena,
+            settings.player,
+            &mut transform,
+            &mut vel.0,
+            time.delta_seconds(),
+        );
+    }
+}
+
+fn enclose(arena: Res<Arena>, mut player: Query<&mut Transform, With<Player>>) {
+    for mut transform in &mut player {
+        let pos = transform.translation.xy();
+        if arena.contains(pos) {
+            continue;
+        }
+        let inside = if arena.wraps() {
+            arena.wrap(pos)
+        } else {
+            arena.edge_point(pos) + arena.normal(pos) * RESHAPE_MARGIN
+        };
+        transform.translation = inside.extend(transform.translation.z);
+    }
+}
+
+fn hazard(
+    mut commands: Commands,
+    settings: Res<BoundarySettings>,
+    arena: Res<Arena>,
+    mut player: Query<(Entity, &Transform, Option<&mut Exposure>), With<Player>>,
+    mut damage: EventWriter<health::Damage>,
+    time: Res<Time>,
+) {
+    let Boundary::Damage {
+        amount, interval, ..
+    } = settings.player
+    else {
+        return;
+    };
+
+    for (entity, transform, exposure) in &mut player {
+        if arena.wraps() || arena.contains(transform.translation.xy()) {
+            if exposure.is_some() {
+                commands.entity(entity).remove::<Exposure>();
+            }
+            continue;
+        }
+
+        let Some(mut exposure) = exposure else {
+            commands.entity(entity).insert(Exposure(0.0));
+            continue;
+        };
+        exposure.0 += time.delta_seconds();
+        if exposure.0 >= interval {
+            exposure.0 -= interval;
+            damage.send(health::Damage {
+                target: entity,
+                attacker: None,
+                amount,
+                kind: DamageKind::Hazard,
+            });
+        }
+    }
+}
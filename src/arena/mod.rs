@@ -0,0 +1,524 @@
+mod boundary;
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use rand::Rng;
+
+use crate::{track::Tracked, Wave};
+
+pub use boundary::{Boundary, BoundarySettings};
+
+const AWAY_ATTEMPTS: usize = 16;
+const POLYGON_ATTEMPTS: usize = 32;
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.init_resource::<Arena>();
+        app.init_resource::<ArenaSettings>();
+        app.add_systems(PreUpdate, reshape.run_if(on_event::<Event>()));
+        app.add_systems(
+            Update,
+            level.run_if(resource_changed::<Wave>().or_else(resource_changed::<ArenaSettings>())),
+        );
+        app.add_systems(PostUpdate, gizmo);
+        app.add_plugins(boundary::Plugin);
+    }
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Reshape(ArenaShape),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArenaShape {
+    Rect(Rect),
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    Polygon(Vec<Vec2>),
+    /// A rectangle whose opposite edges are joined.
+    Torus(Rect),
+}
+
+impl Default for ArenaShape {
+    fn default() -> Self {
+        ArenaShape::Rect(Rect::new(-500., -300., 500., 300.))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub wave: u32,
+    pub shape: ArenaShape,
+    pub boundary: BoundarySettings,
+}
+
+#[derive(Resource)]
+pub struct ArenaSettings {
+    pub levels: Vec<Level>,
+}
+
+impl Default for ArenaSettings {
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                Level {
+                    wave: 0,
+                    shape: ArenaShape::default(),
+                    boundary: BoundarySettings::default(),
+                },
+                Level {
+                    wave: 15,
+                    shape: ArenaShape::Circle {
+                        center: Vec2::ZERO,
+                        radius: 350.,
+                    },
+                    boundary: BoundarySettings {
+                        player: Boundary::Damage {
+                            amount: 1,
+                            interval: 1.0,
+                            force: 300.0,
+                        },
+                        ..default()
+                    },
+                },
+                Level {
+                    wave: 30,
+                    shape: ArenaShape::Polygon(vec![
+                        Vec2::new(-500., 0.),
+                        Vec2::new(-250., 300.),
+                        Vec2::new(250., 300.),
+                        Vec2::new(500., 0.),
+                        Vec2::new(250., -300.),
+                        Vec2::new(-250., -300.),
+                    ]),
+                    boundary: BoundarySettings {
+                        boids: Boundary::Bounce,
+                        player: Boundary::Wall,
+                    },
+                },
+                Level {
+                    wave: 45,
+                    shape: ArenaShape::Torus(Rect::new(-500., -300., 500., 300.)),
+                    boundary: BoundarySettings::default(),
+                },
+            ],
+        }
+    }
+}
+
+impl ArenaSettings {
+    fn level(&self, wave: u32) -> Option<&Level> {
+        self.levels.iter().rfind(|level| level.wave <= wave)
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Arena {
+    pub shape: ArenaShape,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Placement {
+    Uniform,
+    Edge,
+    Away(f32),
+    Ring { inner: f32, outer: f32 },
+}
+
+impl Arena {
+    pub fn bounds(&self) -> Rect {
+        match &self.shape {
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => *rect,
+            ArenaShape::Circle { center, radius } => {
+                Rect::from_center_half_size(*center, Vec2::splat(*radius))
+            }
+            ArenaShape::Polygon(points) => points.iter().fold(
+                Rect::from_center_size(points.first().copied().unwrap_or_default(), Vec2::ZERO),
+                |rect, point| rect.union_point(*point),
+            ),
+        }
+    }
+
+    pub fn wraps(&self) -> bool {
+        matches!(self.shape, ArenaShape::Torus(_))
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        match &self.shape {
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => rect.contains(pos),
+            ArenaShape::Circle { center, radius } => {
+                pos.distance_squared(*center) <= radius * radius
+            }
+            ArenaShape::Polygon(points) => {
+                let mut inside = false;
+                for (a, b) in edges(points) {
+                    if (a.y > pos.y) != (b.y > pos.y)
+                        && pos.x < a.x + (pos.y - a.y) / (b.y - a.y) * (b.x - a.x)
+                    {
+                        inside = !inside;
+                    }
+                }
+                inside
+            }
+        }
+    }
+
+    pub fn clamp(&self, pos: Vec2) -> Vec2 {
+        match &self.shape {
+            ArenaShape::Rect(rect) => pos.clamp(rect.min, rect.max),
+            ArenaShape::Torus(_) => self.wrap(pos),
+            ArenaShape::Circle { center, radius } => {
+                *center + (pos - *center).clamp_length_max(*radius)
+            }
+            ArenaShape::Polygon(_) => {
+                if self.contains(pos) {
+                    pos
+                } else {
+                    self.edge_point(pos)
+                }
+            }
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        match &self.shape {
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => rect.center(),
+            ArenaShape::Circle { center, .. } => *center,
+            #[allow(clippy::cast_precision_loss)]
+            ArenaShape::Polygon(points) => points.iter().sum::<Vec2>() / points.len() as f32,
+        }
+    }
+
+    pub fn edge_point(&self, pos: Vec2) -> Vec2 {
+        match &self.shape {
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => {
+                if !rect.contains(pos) {
+                    return pos.clamp(rect.min, rect.max);
+                }
+                [
+                    Vec2::new(rect.min.x, pos.y),
+                    Vec2::new(rect.max.x, pos.y),
+                    Vec2::new(pos.x, rect.min.y),
+                    Vec2::new(pos.x, rect.max.y),
+                ]
+                .into_iter()
+                .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+                .unwrap_or(pos)
+            }
+            ArenaShape::Circle { center, radius } => {
+                *center + (pos - *center).normalize_or_zero() * *radius
+            }
+            ArenaShape::Polygon(points) => edges(points)
+                .map(|(a, b)| {
+                    let edge = b - a;
+                    let t = ((pos - a).dot(edge) / edge.length_squared()).clamp(0.0, 1.0);
+                    a + edge * t
+                })
+                .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+                .unwrap_or(pos),
+        }
+    }
+
+    /// Distance to the edge, positive inside the arena and negative outside.
+    pub fn depth(&self, pos: Vec2) -> f32 {
+        let distance = pos.distance(self.edge_point(pos));
+        if self.contains(pos) {
+            distance
+        } else {
+            -distance
+        }
+    }
+
+    pub fn normal(&self, pos: Vec2) -> Vec2 {
+        let edge = self.edge_point(pos);
+        let normal = if self.contains(pos) {
+            pos - edge
+        } else {
+            edge - pos
+        };
+        normal
+            .try_normalize()
+            .unwrap_or_else(|| (self.center() - pos).normalize_or_zero())
+    }
+
+    pub fn wrap(&self, pos: Vec2) -> Vec2 {
+        let ArenaShape::Torus(rect) = &self.shape else {
+            return pos;
+        };
+        rect.min + (pos - rect.min).rem_euclid(rect.size())
+    }
+
+    pub fn delta(&self, from: Vec2, to: Vec2) -> Vec2 {
+        let offset = to - from;
+        let ArenaShape::Torus(rect) = &self.shape else {
+            return offset;
+        };
+        let size = rect.size();
+        offset - size * (offset / size).round()
+    }
+
+    /// Also finds neighbors across the seams of a torus, moved next to `pos`.
+    pub fn within_distance(
+        &self,
+        quadtree: &KDTree2<Tracked>,
+        pos: Vec2,
+        distance: f32,
+    ) -> Vec<(Vec2, Option<Entity>)> {
+        let mut nearby = quadtree.within_distance(pos, distance);
+        let ArenaShape::Torus(rect) = &self.shape else {
+            return nearby;
+        };
+
+        let size = rect.size();
+        let reach = rect.inset(distance);
+        for x in -1..=1 {
+            for y in -1..=1 {
+                if x == 0 && y == 0 {
+                    continue;
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let shift = Vec2::new(x as f32, y as f32) * size;
+                let ghost = pos + shift;
+                if !reach.contains(ghost) {
+                    continue;
+                }
+                nearby.extend(
+                    quadtree
+                        .within_distance(ghost, distance)
+                        .into_iter()
+                        .map(|(other, entity)| (other - shift, entity)),
+                );
+            }
+        }
+        nearby
+    }
+
+    pub fn sample(&self, placement: Placement, origin: Vec2, rng: &mut impl Rng) -> Vec2 {
+        match placement {
+            Placement::Uniform => self.uniform(rng),
+            Placement::Edge => self.edge(rng),
+            Placement::Away(distance) => self.away_from(origin, distance, rng),
+            Placement::Ring { inner, outer } => self.ring(origin, inner, outer, rng),
+        }
+    }
+
+    pub fn uniform(&self, rng: &mut impl Rng) -> Vec2 {
+        match &self.shape {
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => {
+                rect.min + rect.size() * Vec2::new(rng.gen(), rng.gen())
+            }
+            ArenaShape::Circle { center, radius } => {
+                let angle = rng.gen::<f32>() * TAU;
+                *center + Vec2::from_angle(angle) * *radius * rng.gen::<f32>().sqrt()
+            }
+            ArenaShape::Polygon(_) => {
+                let bounds = self.bounds();
+                (0..POLYGON_ATTEMPTS)
+                    .map(|_| bounds.min + bounds.size() * Vec2::new(rng.gen(), rng.gen()))
+                    .find(|pos| self.contains(*pos))
+                    .unwrap_or_else(|| self.center())
+            }
+        }
+    }
+
+    pub fn away_from(&self, point: Vec2, distance: f32, rng: &mut impl Rng) -> Vec2 {
+        let mut best = self.uniform(rng);
+        for _ in 1..AWAY_ATTEMPTS {
+            if self.delta(point, best).length_squared() >= distance * distance {
+                break;
+            }
+            let candidate = self.uniform(rng);
+            if self.delta(point, candidate).length_squared()
+                > self.delta(point, best).length_squared()
+            {
+                best = candidate;
+            }
+        }
+        best
+    }
+
+    pub fn edge(&self, rng: &mut impl Rng) -> Vec2 {
+        let points = match &self.shape {
+            ArenaShape::Circle { center, radius } => {
+                return *center + Vec2::from_angle(rng.gen::<f32>() * TAU) * *radius;
+            }
+            ArenaShape::Rect(rect) | ArenaShape::Torus(rect) => vec![
+                rect.min,
+                Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vec2::new(rect.min.x, rect.max.y),
+            ],
+            ArenaShape::Polygon(points) => points.clone(),
+        };
+
+        let perimeter: f32 = edges(&points).map(|(a, b)| a.distance(b)).sum();
+        let mut along = rng.gen::<f32>() * perimeter;
+        for (a, b) in edges(&points) {
+            let length = a.distance(b);
+            if along <= length {
+                return a + (b - a).normalize_or_zero() * along;
+            }
+            along -= length;
+        }
+        points.first().copied().unwrap_or_default()
+    }
+
+    pub fn ring(&self, center: Vec2, inner: f32, outer: f32, rng: &mut impl Rng) -> Vec2 {
+        let angle = rng.gen::<f32>() * TAU;
+        let radius = rng
+            .gen_range(inner.min(outer).powi(2)..=outer.powi(2))
+            .sqrt();
+        self.clamp(center + Vec2::from_angle(angle) * radius)
+    }
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+}
+
+fn reshape(mut arena: ResMut<Arena>, mut events: EventReader<Event>) {
+    for event in events.read() {
+        let Event::Reshape(shape) = event;
+        arena.shape = shape.clone();
+    }
+}
+
+fn level(
+    settings: Res<ArenaSettings>,
+    wave: Res<Wave>,
+    arena: Res<Arena>,
+    mut boundary: ResMut<BoundarySettings>,
+    mut events: EventWriter<Event>,
+) {
+    let Some(level) = settings.level(wave.0) else {
+        return;
+    };
+    if level.shape != arena.shape {
+        events.send(Event::Reshape(level.shape.clone()));
+    }
+    boundary.set_if_neq(level.boundary.clone());
+}
+
+fn gizmo(mut gizmos: Gizmos, arena: Res<Arena>) {
+    let color = Color::rgba(1.0, 1.0, 1.0, 0.1);
+    match &arena.shape {
+        ArenaShape::Rect(rect) => gizmos.rect_2d(rect.center(), 0.0, rect.size(), color),
+        ArenaShape::Torus(rect) => gizmos.rect_2d(
+            rect.center(),
+            0.0,
+            rect.size(),
+            Color::rgba(0.5, 0.5, 1.0, 0.1),
+        ),
+        ArenaShape::Circle { center, radius } => {
+            gizmos.circle_2d(*center, *radius, color).segments(64);
+        }
+        ArenaShape::Polygon(points) => {
+            gizmos.linestrip_2d(points.iter().copied().chain(points.first().copied()), color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn rect() -> Arena {
+        Arena {
+            shape: ArenaShape::Rect(Rect::new(-100.0, -50.0, 100.0, 50.0)),
+        }
+    }
+
+    #[test]
+    fn rect_contains_and_clamps() {
+        let arena = rect();
+        assert!(arena.contains(Vec2::new(100.0, 0.0)));
+        assert!(!arena.contains(Vec2::new(100.1, 0.0)));
+        assert_eq!(
+            arena.clamp(Vec2::new(150.0, -80.0)),
+            Vec2::new(100.0, -50.0)
+        );
+        assert_eq!(
+            arena.edge_point(Vec2::new(90.0, 0.0)),
+            Vec2::new(100.0, 0.0)
+        );
+        assert_eq!(arena.normal(Vec2::new(90.0, 0.0)), Vec2::NEG_X);
+    }
+
+    #[test]
+    fn circle_contains() {
+        let arena = Arena {
+            shape: ArenaShape::Circle {
+                center: Vec2::new(10.0, 0.0),
+                radius: 50.0,
+            },
+        };
+        assert!(arena.contains(Vec2::new(60.0, 0.0)));
+        assert!(!arena.contains(Vec2::new(50.0, 40.0)));
+        assert_eq!(arena.clamp(Vec2::new(110.0, 0.0)), Vec2::new(60.0, 0.0));
+    }
+
+    #[test]
+    fn polygon_edges() {
+        // A concave L shape missing its top right quarter.
+        let arena = Arena {
+            shape: ArenaShape::Polygon(vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(100.0, 0.0),
+                Vec2::new(100.0, 50.0),
+                Vec2::new(50.0, 50.0),
+                Vec2::new(50.0, 100.0),
+                Vec2::new(0.0, 100.0),
+            ]),
+        };
+        assert!(arena.contains(Vec2::new(25.0, 75.0)));
+        assert!(arena.contains(Vec2::new(75.0, 25.0)));
+        assert!(!arena.contains(Vec2::new(75.0, 75.0)));
+        assert_eq!(
+            arena.edge_point(Vec2::new(75.0, 60.0)),
+            Vec2::new(75.0, 50.0)
+        );
+        assert!(arena.depth(Vec2::new(75.0, 60.0)) < 0.0);
+        assert!(arena.depth(Vec2::new(25.0, 25.0)) > 0.0);
+    }
+
+    #[test]
+    fn torus_wraps() {
+        let arena = Arena {
+            shape: ArenaShape::Torus(Rect::new(-100.0, -50.0, 100.0, 50.0)),
+        };
+        assert_eq!(arena.wrap(Vec2::new(110.0, -60.0)), Vec2::new(-90.0, 40.0));
+        assert_eq!(arena.wrap(Vec2::new(10.0, 20.0)), Vec2::new(10.0, 20.0));
+        assert_eq!(
+            arena.delta(Vec2::new(90.0, 0.0), Vec2::new(-90.0, 0.0)),
+            Vec2::new(20.0, 0.0)
+        );
+        assert_eq!(
+            rect().delta(Vec2::new(90.0, 0.0), Vec2::new(-90.0, 0.0)),
+            Vec2::new(-180.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn ring_with_inner_past_outer() {
+        let arena = Arena::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..16 {
+            let pos = arena.ring(Vec2::ZERO, 200.0, 50.0, &mut rng);
+            assert!(pos.length() <= 50.0 + 1e-3);
+        }
+    }
+}
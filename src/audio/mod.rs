@@ -0,0 +1,235 @@
+mod music;
+mod spatial;
+
+use std::collections::HashMap;
+
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::assets::Sounds;
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.init_resource::<AudioRng>();
+        app.add_plugins(music::Plugin);
+        app.add_plugins(spatial::Plugin);
+        app.insert_resource(Mixer {
+            master: 1.0,
+            buses: HashMap::from([(Bus::Sfx, 1.0), (Bus::Music, 1.0), (Bus::Engine, 1.0)]),
+            groups: HashMap::from([
+                (SoundGroup::Boost, GroupSettings::new(Bus::Sfx, 2)),
+                (SoundGroup::Collect, GroupSettings::new(Bus::Sfx, 4)),
+                (SoundGroup::GameOver, GroupSettings::new(Bus::Sfx, 1)),
+            ]),
+        });
+        app.add_systems(Update, play.run_if(on_event::<Event>()));
+        app.add_systems(Update, volume.run_if(resource_changed::<Mixer>()));
+    }
+}
+
+/// Voice limits depend on real time, so variance rolls stay off `RngSource`.
+#[derive(Resource, Deref, DerefMut)]
+struct AudioRng(ChaCha8Rng);
+
+impl Default for AudioRng {
+    fn default() -> Self {
+        Self(ChaCha8Rng::from_entropy())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Bus {
+    Sfx,
+    Music,
+    Engine,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SoundGroup {
+    Boost,
+    Collect,
+    GameOver,
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Play { group: SoundGroup, position: Vec2 },
+}
+
+pub struct GroupSettings {
+    pub bus: Bus,
+    pub max_voices: usize,
+    pub volume: f32,
+    pub volume_variance: f32,
+    pub pitch_variance: f32,
+    pub spatial: bool,
+}
+
+impl GroupSettings {
+    pub fn new(bus: Bus, max_voices: usize) -> Self {
+        Self {
+            bus,
+            max_voices,
+            volume: 1.0,
+            volume_variance: 0.1,
+            pitch_variance: 0.05,
+            spatial: true,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Mixer {
+    pub master: f32,
+    pub buses: HashMap<Bus, f32>,
+    pub groups: HashMap<SoundGroup, GroupSettings>,
+}
+
+impl Mixer {
+    pub fn volume(&self, bus: Bus) -> f32 {
+        self.master * self.buses.get(&bus).copied().unwrap_or(1.0)
+    }
+
+    pub fn settings(&self, channel: &Channel, mode: PlaybackMode) -> PlaybackSettings {
+        PlaybackSettings {
+            mode,
+            volume: Volume::new_absolute(self.volume(channel.bus) * channel.volume),
+            ..default()
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Channel {
+    pub bus: Bus,
+    pub volume: f32,
+}
+
+impl Channel {
+    pub fn new(bus: Bus) -> Self {
+        Self { bus, volume: 1.0 }
+    }
+}
+
+#[derive(Component)]
+struct Voice(SoundGroup);
+
+impl Sounds {
+    fn group(&self, group: SoundGroup) -> &[Handle<AudioSource>] {
+        match group {
+            SoundGroup::Boost => &self.boost,
+            SoundGroup::Collect => &self.collect,
+            SoundGroup::GameOver => &self.gameover,
+        }
+    }
+}
+
+fn play(
+    mut commands: Commands,
+    mixer: Res<Mixer>,
+    sounds: Res<Sounds>,
+    mut rng: ResMut<AudioRng>,
+    mut events: EventReader<Event>,
+    voices: Query<&Voice>,
+) {
+    let mut playing: HashMap<SoundGroup, usize> = HashMap::new();
+    for voice in &voices {
+        *playing.entry(voice.0).or_default() += 1;
+    }
+
+    for event in events.read() {
+        let Event::Play { group, position } = event;
+        let Some(settings) = mixer.groups.get(group) else {
+            continue;
+        };
+
+        let count = playing.entry(*group).or_default();
+        if *count >= settings.max_voices {
+            continue;
+        }
+        *count += 1;
+
+        let Some(source) = sounds.group(*group).choose(&mut **rng) else {
+            continue;
+        };
+
+        let channel = Channel {
+            bus: settings.bus,
+            volume: settings.volume
+                * (1.0 + rng.gen_range(-1.0..=1.0) * settings.volume_variance),
+        };
+        let mut playback = mixer.settings(&channel, PlaybackMode::Despawn);
+        playback.speed = 1.0 + rng.gen_range(-1.0..=1.0) * settings.pitch_variance;
+        playback.spatial = settings.spatial;
+
+        let mut entity = commands.spawn_empty();
+        entity.insert(Name::new("Voice"));
+        entity.insert(Voice(*group));
+        entity.insert(channel);
+        entity.insert(TransformBundle::from_transform(Transform::from_translation(
+            position.extend(0.0),
+        )));
+        entity.insert(AudioBundle {
+            source: source.clone(),
+            settings: playback,
+        });
+    }
+}
+
+fn volume(
+    mixer: Res<Mixer>,
+    sinks: Query<(&AudioSink, &Channel)>,
+    spatial_sinks: Query<(&SpatialAudioSink, &Channel)>,
+) {
+    for (sink, channel) in &sinks {
+        sink.set_volume(mixer.volume(channel.bus) * channel.volume);
+    }
+    for (sink, channel) in &spatial_sinks {
+        sink.set_volume(mixer.volume(channel.bus) * channel.volume);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::RngSource;
+
+    #[test]
+    fn play_leaves_gameplay_rng_alone() {
+        let mut app = App::new();
+        app.add_event::<Event>();
+        app.init_resource::<AudioRng>();
+        app.insert_resource(RngSource::seeded(0));
+        app.insert_resource(Mixer {
+            master: 1.0,
+            buses: HashMap::new(),
+            groups: HashMap::from([(SoundGroup::Collect, GroupSettings::new(Bus::Sfx, 4))]),
+        });
+        app.insert_resource(Sounds {
+            boost: Vec::new(),
+            gameover: Vec::new(),
+            collect: vec![Handle::default(), Handle::default()],
+            player_engine: Handle::default(),
+        });
+        app.add_systems(Update, play);
+
+        let before = app.world.resource::<RngSource>().get_word_pos();
+        for _ in 0..3 {
+            app.world.send_event(Event::Play {
+                group: SoundGroup::Collect,
+                position: Vec2::ZERO,
+            });
+        }
+        app.update();
+
+        assert_eq!(app.world.query::<&Voice>().iter(&app.world).count(), 3);
+        assert_eq!(app.world.resource::<RngSource>().get_word_pos(), before);
+    }
+}
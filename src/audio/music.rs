@@ -0,0 +1,352 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::{
+    audio::{AddAudioSource, PlaybackMode, Source},
+    prelude::*,
+};
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use interpolation::Lerp;
+
+use crate::{
+    boid::{AngryBoi, Boid, BoidSettings},
+    player::{Boost, Player},
+    track::Tracked,
+    GameState, Restart,
+};
+
+use super::{Bus, Channel, Mixer};
+
+const SAMPLE_RATE: u32 = 44_100;
+const PAUSE_DUCK: f32 = 0.3;
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Synth>();
+        app.add_systems(Startup, setup);
+        app.add_systems(Update, intensity);
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            stinger.run_if(not(resource_exists::<Restart>())),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Layer {
+    Pad,
+    Bass,
+    Arp,
+    Drums,
+}
+
+#[derive(Component)]
+struct Stem {
+    layer: Layer,
+    level: f32,
+}
+
+#[derive(Resource)]
+struct Music {
+    stinger: Handle<Synth>,
+}
+
+#[derive(Copy, Clone)]
+pub(super) enum Waveform {
+    Sine,
+    Triangle,
+    Square,
+    Noise,
+}
+
+#[derive(Copy, Clone)]
+pub(super) enum Envelope {
+    Pluck,
+    Swell,
+    Sustain,
+}
+
+#[derive(Asset, TypePath, Clone)]
+pub struct Synth {
+    steps: Vec<Vec<f32>>,
+    step: f32,
+    gain: f32,
+    waveform: Waveform,
+    envelope: Envelope,
+    looping: bool,
+}
+
+impl Synth {
+    pub(super) fn new(steps: &[&[u8]], step: f32, waveform: Waveform, envelope: Envelope) -> Self {
+        Self {
+            steps: steps
+                .iter()
+                .map(|notes| notes.iter().copied().map(frequency).collect())
+                .collect(),
+            step,
+            gain: 0.2,
+            waveform,
+            envelope,
+            looping: true,
+        }
+    }
+
+    pub(super) fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+}
+
+fn frequency(note: u8) -> f32 {
+    440.0 * 2f32.powf((f32::from(note) - 69.0) / 12.0)
+}
+
+pub struct SynthDecoder {
+    synth: Synth,
+    sample: u32,
+    length: u32,
+    phases: Vec<f32>,
+    noise: u32,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self.sample as f32 / SAMPLE_RATE as f32;
+        let index = (time / self.synth.step) as usize;
+        if !self.synth.looping && index >= self.synth.steps.len() {
+            return None;
+        }
+        self.sample += 1;
+        if self.synth.looping {
+            self.sample %= self.length;
+        }
+
+        let notes = &self.synth.steps[index % self.synth.steps.len()];
+        let progress = time / self.synth.step - index as f32;
+        let envelope = match self.synth.envelope {
+            Envelope::Pluck => (progress * 100.0).min(1.0) * (1.0 - progress).powi(3),
+            Envelope::Swell => (progress * PI).sin(),
+            Envelope::Sustain => 1.0,
+        };
+
+        let mut value = 0.0;
+        for (phase, frequency) in self.phases.iter_mut().zip(notes) {
+            *phase = (*phase + frequency / SAMPLE_RATE as f32) % 1.0;
+            value += match self.synth.waveform {
+                Waveform::Sine => (*phase * PI * 2.0).sin(),
+                Waveform::Triangle => 1.0 - 4.0 * (*phase - 0.5).abs(),
+                Waveform::Square => {
+                    if *phase < 0.5 {
+                        1.0
+                    } else {
+                        -1.0
+                    }
+                }
+                Waveform::Noise => {
+                    self.noise ^= self.noise << 13;
+                    self.noise ^= self.noise >> 17;
+                    self.noise ^= self.noise << 5;
+                    self.noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+                }
+            };
+        }
+
+        Some(value * envelope * self.synth.gain)
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        #[allow(clippy::cast_precision_loss)]
+        (!self.synth.looping)
+            .then(|| Duration::from_secs_f32(self.synth.steps.len() as f32 * self.synth.step))
+    }
+}
+
+impl Decodable for Synth {
+    type Decoder = SynthDecoder;
+
+    type DecoderItem = <SynthDecoder as Iterator>::Item;
+
+    fn decoder(&self) -> Self::Decoder {
+        let voices = self.steps.iter().map(Vec::len).max().unwrap_or_default();
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let length = (self.steps.len() as f32 * self.step * SAMPLE_RATE as f32) as u32;
+        SynthDecoder {
+            synth: self.clone(),
+            sample: 0,
+            length,
+            phases: vec![0.0; voices],
+            noise: 0x2545_f491,
+        }
+    }
+}
+
+// Am - F - C - G, one bar each at 120 bpm.
+const CHORDS: [[u8; 3]; 4] = [[57, 60, 64], [53, 57, 60], [48, 52, 55], [55, 59, 62]];
+
+fn setup(mut commands: Commands, mut synths: ResMut<Assets<Synth>>, mixer: Res<Mixer>) {
+    let pad: Vec<&[u8]> = CHORDS.iter().map(<[u8; 3]>::as_slice).collect();
+    let bass: Vec<[u8; 1]> = CHORDS
+        .iter()
+        .flat_map(|chord| [[chord[0] - 12]; 8])
+        .collect();
+    let arp: Vec<[u8; 1]> = CHORDS
+        .iter()
+        .flat_map(|chord| (0..16).map(|i| [chord[i % 3] + 12]))
+        .collect();
+    let drums: Vec<&[u8]> = (0..64)
+        .map(|i| if i % 4 == 2 { &[100][..] } else { &[][..] })
+        .collect();
+
+    let stems = [
+        (
+            Layer::Pad,
+            Synth::new(&pad, 2.0, Waveform::Sine, Envelope::Swell).with_gain(0.12),
+        ),
+        (
+            Layer::Bass,
+            Synth::new(
+                &bass.iter().map(<[u8; 1]>::as_slice).collect::<Vec<_>>(),
+                0.25,
+                Waveform::Triangle,
+                Envelope::Pluck,
+            )
+            .with_gain(0.35),
+        ),
+        (
+            Layer::Arp,
+            Synth::new(
+                &arp.iter().map(<[u8; 1]>::as_slice).collect::<Vec<_>>(),
+                0.125,
+                Waveform::Square,
+                Envelope::Pluck,
+            )
+            .with_gain(0.06),
+        ),
+        (
+            Layer::Drums,
+            Synth::new(&drums, 0.125, Waveform::Noise, Envelope::Pluck).with_gain(0.15),
+        ),
+    ];
+
+    for (layer, synth) in stems {
+        let channel = Channel {
+            bus: Bus::Music,
+            volume: 0.0,
+        };
+        let mut entity = commands.spawn_empty();
+        entity.insert(Name::new("Stem"));
+        entity.insert(AudioSourceBundle {
+            source: synths.add(synth),
+            settings: mixer.settings(&channel, PlaybackMode::Loop),
+        });
+        entity.insert(Stem { layer, level: 0.0 });
+        entity.insert(channel);
+    }
+
+    commands.insert_resource(Music {
+        stinger: synths.add(
+            Synth::new(
+                &[&[69], &[65], &[62], &[57], &[57], &[57]],
+                0.15,
+                Waveform::Square,
+                Envelope::Pluck,
+            )
+            .with_gain(0.15)
+            .once(),
+        ),
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn intensity(
+    mixer: Res<Mixer>,
+    settings: Res<BoidSettings>,
+    state: Res<State<GameState>>,
+    quadtree: Res<KDTree2<Tracked>>,
+    boids: Query<(), With<Boid>>,
+    angry: Query<(), With<AngryBoi>>,
+    players: Query<(&Transform, &Boost), With<Player>>,
+    mut stems: Query<(&mut Stem, &mut Channel, &AudioSink)>,
+    time: Res<Time<Real>>,
+) {
+    let (threats, boosting) =
+        players
+            .iter()
+            .fold((0, false), |(threats, boosting), (transform, boost)| {
+                let nearby = quadtree
+                    .within_distance(transform.translation.xy(), settings.home_range)
+                    .into_iter()
+                    .filter_map(|(_pos, entity)| entity)
+                    .filter(|entity| angry.contains(*entity))
+                    .count();
+                (threats.max(nearby), boosting || boost.active())
+            });
+
+    #[allow(clippy::cast_precision_loss)]
+    let population = boids.iter().len() as f32;
+    #[allow(clippy::cast_precision_loss)]
+    let threats = threats as f32;
+
+    let duck = match state.get() {
+        GameState::Playing => 1.0,
+        GameState::Paused | GameState::Connecting => PAUSE_DUCK,
+        GameState::GameOver => 0.0,
+    };
+
+    for (mut stem, mut channel, sink) in &mut stems {
+        let target = match stem.layer {
+            Layer::Pad => 1.0,
+            Layer::Bass => (population / 200.0).clamp(0.0, 1.0),
+            Layer::Arp => (threats / 5.0).clamp(0.0, 1.0),
+            Layer::Drums => {
+                if boosting {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        } * duck;
+
+        stem.level = stem
+            .level
+            .lerp(&target, &(time.delta_seconds() * 2.0).min(1.0));
+        channel.volume = stem.level;
+        sink.set_volume(mixer.volume(channel.bus) * channel.volume);
+    }
+}
+
+fn stinger(mut commands: Commands, music: Res<Music>, mixer: Res<Mixer>) {
+    let channel = Channel::new(Bus::Music);
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Stinger"));
+    entity.insert(AudioSourceBundle {
+        source: music.stinger.clone(),
+        settings: mixer.settings(&channel, PlaybackMode::Despawn),
+    });
+    entity.insert(channel);
+}
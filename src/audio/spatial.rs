@@ -0,0 +1,160 @@
+use bevy::{
+    audio::{PlaybackMode, SpatialScale},
+    prelude::*,
+};
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use interpolation::Lerp;
+
+use crate::{boid::Boid, camera::PlayerCamera, player::Player, track::Tracked, GameState};
+
+use super::{
+    music::{Envelope, Synth, Waveform},
+    Bus, Channel, Mixer,
+};
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialScale::new_2d(1.0 / 300.0));
+        app.insert_resource(SpatialSettings {
+            ear_gap: 400.0,
+            player_bias: 0.5,
+            hum: true,
+            hum_range: 150.0,
+            hum_density: 60.0,
+        });
+        app.add_systems(Startup, setup);
+        app.add_systems(Update, listener);
+        app.add_systems(Update, hum);
+    }
+}
+
+#[derive(Resource)]
+pub struct SpatialSettings {
+    pub ear_gap: f32,
+    pub player_bias: f32,
+    pub hum: bool,
+    pub hum_range: f32,
+    pub hum_density: f32,
+}
+
+#[derive(Component)]
+struct Listener;
+
+#[derive(Component, Default)]
+struct Hum {
+    level: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut synths: ResMut<Assets<Synth>>,
+    settings: Res<SpatialSettings>,
+    mixer: Res<Mixer>,
+) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Listener"));
+    entity.insert(Listener);
+    entity.insert(SpatialListener::new(settings.ear_gap));
+    entity.insert(TransformBundle::default());
+
+    let channel = Channel {
+        bus: Bus::Sfx,
+        volume: 0.0,
+    };
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Hum"));
+    entity.insert(Hum::default());
+    entity.insert(AudioSourceBundle {
+        source: synths.add(
+            Synth::new(&[&[33, 40, 45]], 1.0, Waveform::Triangle, Envelope::Sustain)
+                .with_gain(0.1),
+        ),
+        settings: mixer
+            .settings(&channel, PlaybackMode::Loop)
+            .with_spatial(true),
+    });
+    entity.insert(channel);
+    entity.insert(TransformBundle::default());
+}
+
+#[allow(clippy::type_complexity)]
+fn listener(
+    settings: Res<SpatialSettings>,
+    camera: Query<&Transform, (With<PlayerCamera>, Without<Listener>)>,
+    players: Query<&Transform, (With<Player>, Without<Listener>)>,
+    mut listener: Query<(&mut Transform, &mut SpatialListener), With<Listener>>,
+) {
+    let Ok((mut transform, mut ears)) = listener.get_single_mut() else {
+        return;
+    };
+
+    let camera = camera
+        .get_single()
+        .map(|camera| camera.translation.xy())
+        .unwrap_or_default();
+    #[allow(clippy::cast_precision_loss)]
+    let count = players.iter().len() as f32;
+    let position = if count > 0.0 {
+        let players = players
+            .iter()
+            .map(|player| player.translation.xy())
+            .sum::<Vec2>()
+            / count;
+        camera.lerp(players, settings.player_bias)
+    } else {
+        camera
+    };
+    transform.translation = position.extend(0.0);
+
+    if settings.is_changed() {
+        *ears = SpatialListener::new(settings.ear_gap);
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn hum(
+    mixer: Res<Mixer>,
+    settings: Res<SpatialSettings>,
+    state: Res<State<GameState>>,
+    quadtree: Res<KDTree2<Tracked>>,
+    boids: Query<(), With<Boid>>,
+    players: Query<&Transform, (With<Player>, Without<Hum>)>,
+    mut hum: Query<(&mut Hum, &mut Channel, &mut Transform, &SpatialAudioSink)>,
+    time: Res<Time<Real>>,
+) {
+    let Ok((mut hum, mut channel, mut transform, sink)) = hum.get_single_mut() else {
+        return;
+    };
+
+    let mut target = 0.0;
+    if let (true, GameState::Playing) = (settings.hum, state.get()) {
+        // Hum with the densest flock around any player.
+        let nearby: Vec<Vec2> = players
+            .iter()
+            .map(|player| {
+                quadtree
+                    .within_distance(player.translation.xy(), settings.hum_range)
+                    .into_iter()
+                    .filter_map(|(pos, entity)| entity.map(|entity| (pos, entity)))
+                    .filter(|(_pos, entity)| boids.contains(*entity))
+                    .map(|(pos, _entity)| pos)
+                    .collect::<Vec<_>>()
+            })
+            .max_by_key(Vec::len)
+            .unwrap_or_default();
+
+        if !nearby.is_empty() {
+            #[allow(clippy::cast_precision_loss)]
+            let count = nearby.len() as f32;
+            transform.translation = (nearby.iter().sum::<Vec2>() / count).extend(0.0);
+            target = (count / settings.hum_density).min(1.0);
+        }
+    }
+
+    hum.level = hum.level.lerp(&target, &(time.delta_seconds() * 3.0).min(1.0));
+    channel.volume = hum.level;
+    sink.set_volume(mixer.volume(channel.bus) * channel.volume);
+    sink.set_speed(0.8 + hum.level * 0.4);
+}
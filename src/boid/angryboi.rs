@@ -1,13 +1,20 @@
 use bevy::prelude::*;
 
-use crate::{assets::Images, health::damages};
+use crate::{
+    assets::Images,
+    difficulty::Difficulty,
+    health::{DamageKind, Damager},
+    hitbox::{Hitbox, Layer, Shape},
+    indicator::OffscreenIndicator,
+    trail::Trail,
+};
 
 use crate::player::Player;
 
 use super::{BoidBundle, Home};
 
 #[derive(Component, Default)]
-struct AngryBoi;
+pub struct AngryBoi;
 
 pub(super) struct Plugin;
 
@@ -15,23 +22,41 @@ impl bevy::prelude::Plugin for Plugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, spawn.run_if(on_event::<super::SpawnEvent>()));
         app.add_systems(Update, super::home::<Player>);
-        app.add_systems(Update, damages::<AngryBoi, Player, 16>);
     }
 }
 
-fn spawn(mut commands: Commands, images: Res<Images>, mut events: EventReader<super::SpawnEvent>) {
+fn spawn(
+    mut commands: Commands,
+    images: Res<Images>,
+    difficulty: Res<Difficulty>,
+    mut events: EventReader<super::SpawnEvent>,
+) {
     for event in events.read() {
         if let super::BoidKind::AngryBoi = event.kind {
             for _ in 0..event.count {
                 let mut entity = commands.spawn_empty();
                 entity.insert(Name::new("AngryBoi"));
                 entity.insert(AngryBoi);
-                let home: Home<Player> = Home::new(10.0);
+                entity.insert(
+                    OffscreenIndicator::new(Color::RED)
+                        .with_icon("!")
+                        .with_range(400.0),
+                );
+                let home: Home<Player> = Home::new(difficulty.angry_homing).nearest();
                 entity.insert(home);
+                entity.insert(Hitbox {
+                    shape: Shape::Circle(8.0),
+                    layer: Layer::AngryBoi,
+                });
+                entity.insert(Damager {
+                    amount: 1,
+                    kind: DamageKind::Collision,
+                });
                 entity.insert(SpriteBundle {
                     texture: images.angryboi.clone(),
                     ..default()
                 });
+                entity.insert(Trail::new(Color::rgba(1.0, 0.2, 0.2, 0.4), 4.0));
                 entity.insert(BoidBundle::new(event.position.extend(2.0), event.velocity));
             }
         }
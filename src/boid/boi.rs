@@ -1,5 +1,5 @@
 use super::BoidBundle;
-use crate::{assets::Images, rng::RngSource};
+use crate::{assets::Images, rng::RngSource, trail::Trail};
 use bevy::prelude::*;
 use rand::Rng;
 
@@ -34,6 +34,7 @@ fn spawn(
                     x: 16. * rng.gen::<f32>() - 8.,
                     y: 16. * rng.gen::<f32>() - 8.,
                 };
+                entity.insert(Trail::new(Color::rgba(0.8, 0.9, 1.0, 0.25), 3.0));
                 entity.insert(BoidBundle::new(
                     (event.position + offset).extend(0.0),
                     event.velocity,
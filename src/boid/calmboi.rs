@@ -1,15 +1,18 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
-use bevy_spatial::kdtree::KDTree2;
-use bevy_spatial::SpatialAccess;
 use rand::Rng;
 
 use crate::assets::Images;
-use crate::collectible::{self, Collectible};
+use crate::collectible::{self, Collectible, CollectibleKind};
+use crate::difficulty::Difficulty;
+use crate::hitbox::{self, Hitbox, Layer, Shape};
+use crate::mode::GameMode;
+use crate::popup;
 
 use crate::shockwave;
-use crate::{rng::RngSource, track::Tracked, GameEvent};
+use crate::trail::Trail;
+use crate::{rng::RngSource, GameEvent};
 
 use super::{BoidBundle, BoidKind, Home, Velocity};
 
@@ -21,7 +24,7 @@ pub(super) struct Plugin;
 impl bevy::prelude::Plugin for Plugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, spawn.run_if(on_event::<super::SpawnEvent>()));
-        app.add_systems(Update, collect);
+        app.add_systems(Update, collect.run_if(on_event::<hitbox::Event>()));
         app.add_systems(
             Update,
             super::home::<Collectible>.run_if(in_state(crate::GameState::Playing)),
@@ -32,6 +35,7 @@ impl bevy::prelude::Plugin for Plugin {
 fn spawn(
     mut commands: Commands,
     images: Res<Images>,
+    difficulty: Res<Difficulty>,
     mut rng: ResMut<RngSource>,
     mut events: EventReader<super::SpawnEvent>,
 ) {
@@ -42,8 +46,12 @@ fn spawn(
 
                 entity.insert(Name::new("CalmBoi"));
                 entity.insert(CalmBoi);
-                let home: Home<Collectible> = Home::new(3.0);
+                let home: Home<Collectible> = Home::new(difficulty.calm_homing);
                 entity.insert(home);
+                entity.insert(Hitbox {
+                    shape: Shape::Circle(16.0),
+                    layer: Layer::CalmBoi,
+                });
                 entity.insert(SpriteBundle {
                     texture: images.calmboi.clone(),
                     ..default()
@@ -53,6 +61,7 @@ fn spawn(
                     x: 16. * rng.gen::<f32>() - 8.,
                     y: 16. * rng.gen::<f32>() - 8.,
                 };
+                entity.insert(Trail::new(Color::rgba(0.4, 0.6, 1.0, 0.35), 3.0));
                 entity.insert(BoidBundle::new(
                     (event.position + offset).extend(1.0),
                     event.velocity,
@@ -65,43 +74,68 @@ fn spawn(
 #[allow(clippy::too_many_arguments)]
 fn collect(
     mut commands: Commands,
-    quadtree: Res<KDTree2<Tracked>>,
-    boid: Query<(Entity, &Transform, &Velocity), With<CalmBoi>>,
-    collectibles: Query<Entity, (With<Collectible>, Without<collectible::Cooldown>)>,
+    mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    boid: Query<(&Transform, &Velocity), With<CalmBoi>>,
+    collectibles: Query<&Collectible, Without<collectible::Cooldown>>,
+    mut overlaps: EventReader<hitbox::Event>,
     mut collectible_event: EventWriter<collectible::Event>,
     mut game_events: EventWriter<GameEvent>,
     mut boi_events: EventWriter<super::SpawnEvent>,
     mut shockwave_events: EventWriter<shockwave::Event>,
+    mut popup_events: EventWriter<popup::Event>,
 ) {
-    for (boid_entity, trans, vel) in boid.iter() {
-        let pos = trans.translation.xy();
-        if let Some((position, entity)) = quadtree
-            .within_distance(pos, 32.0)
-            .into_iter()
-            .filter_map(|(_pos, entity)| entity.map(|entity| (pos, entity)))
-            .find(|(_pos, entity)| collectibles.contains(*entity))
+    for event in overlaps.read() {
+        let hitbox::Event::Overlap {
+            hitbox: boid_entity,
+            hurtbox: entity,
+            layer: Layer::CalmBoi,
+            target: Layer::Collectible,
+            position,
+        } = event
+        else {
+            continue;
+        };
+        let Ok((trans, vel)) = boid.get(*boid_entity) else {
+            continue;
+        };
+        if !collectibles
+            .get(*entity)
+            .is_ok_and(|collectible| collectible.kind == CollectibleKind::Points)
         {
-            collectible_event.send(collectible::Event::Collect(entity));
-            game_events.send(GameEvent::NextWave {
-                position,
-                velocity: vel.0,
-            });
-            commands.entity(boid_entity).despawn();
+            continue;
+        }
+
+        collectible_event.send(collectible::Event::Collect(*entity));
+        game_events.send(GameEvent::NextWave {
+            position: *position,
+            velocity: vel.0,
+        });
+        if mode.rules().angry_waves {
+            commands.entity(*boid_entity).despawn();
             boi_events.send(super::SpawnEvent {
                 kind: BoidKind::AngryBoi,
-                count: 1,
+                count: difficulty.angry_count,
                 position: trans.translation.xy(),
                 velocity: vel.0,
             });
-
-            shockwave_events.send(shockwave::Event::Spawn {
-                position,
-                radius: 100.,
-                duration: Duration::from_secs_f32(1.),
-                color: Color::BLUE,
-                repel: false,
+            popup_events.send(popup::Event::Warning {
+                text: "Angry!",
+                position: trans.translation.xy(),
             });
-            return;
         }
+
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: *position,
+            radius: 100.,
+            duration: Duration::from_secs_f32(1.),
+            color: Color::BLUE,
+            effect: shockwave::Effect::None,
+            falloff: shockwave::Falloff::Constant,
+            affects_player: false,
+            affects_collectibles: false,
+            source: None,
+        });
+        return;
     }
 }
@@ -5,32 +5,24 @@ mod calmboi;
 use std::{f32::consts::PI, marker::PhantomData};
 
 use bevy::prelude::*;
-use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use bevy_spatial::kdtree::KDTree2;
 
-#[cfg(feature = "inspector")]
-use crate::collectible::Collectible;
 #[cfg(feature = "inspector")]
 use bevy_inspector_egui::{prelude::*, quick::ResourceInspectorPlugin, InspectorOptions};
 
-use crate::{track::Tracked, velocity::Velocity};
+use crate::{
+    arena::Arena, collectible::Collectible, difficulty::Difficulty, player::Player,
+    shockwave::Stunned, track::Tracked, velocity::Velocity,
+};
+
+pub use angryboi::AngryBoi;
 
 pub struct BoidPlugin;
 
 impl Plugin for BoidPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnEvent>();
-        app.insert_resource(BoidSettings {
-            coherence: 0.192,
-            separation: 0.487,
-            alignment: 0.435,
-            visual_range: 15.0,
-            avoid_range: 10.0,
-            max_speed: 200.0,
-            bounds: Rect::new(-500., -300., 500., 300.),
-            centering_force: 20.,
-            home_range: 300.,
-            home_effect: 2.,
-        });
+        app.init_resource::<BoidSettings>();
         app.add_systems(OnExit(crate::GameState::GameOver), reset);
         app.add_systems(
             Update,
@@ -41,6 +33,7 @@ impl Plugin for BoidPlugin {
             ),
         );
         app.add_systems(PostUpdate, update);
+        app.add_systems(Update, difficulty.run_if(resource_changed::<Difficulty>()));
         app.add_plugins(boi::Plugin);
         app.add_plugins(calmboi::Plugin);
         app.add_plugins(angryboi::Plugin);
@@ -90,7 +83,7 @@ struct BoidDebugSettings {
     direction: bool,
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Clone)]
 #[cfg_attr(
     feature = "inspector",
     derive(Reflect, InspectorOptions),
@@ -113,13 +106,35 @@ pub struct BoidSettings {
     pub home_effect: f32,
     #[cfg_attr(feature = "inspector", inspector(min = 0.0))]
     pub max_speed: f32,
+    /// Set by the difficulty curve on top of `max_speed`.
     #[cfg_attr(feature = "inspector", inspector(min = 0.0))]
-    pub centering_force: f32,
-    pub bounds: Rect,
+    pub speed_scale: f32,
+}
+
+impl Default for BoidSettings {
+    fn default() -> Self {
+        Self {
+            coherence: 0.192,
+            separation: 0.487,
+            alignment: 0.435,
+            visual_range: 15.0,
+            avoid_range: 10.0,
+            max_speed: 200.0,
+            speed_scale: 1.0,
+            home_range: 300.,
+            home_effect: 2.,
+        }
+    }
+}
+
+impl BoidSettings {
+    pub fn top_speed(&self) -> f32 {
+        self.max_speed * self.speed_scale
+    }
 }
 
 #[derive(Component)]
-struct Boid;
+pub struct Boid;
 
 #[derive(Bundle)]
 struct BoidBundle {
@@ -154,13 +169,14 @@ struct Coherence {
 
 fn coherence(
     settings: Res<BoidSettings>,
+    arena: Res<Arena>,
     quadtree: Res<KDTree2<Tracked>>,
     mut boids: Query<(&Transform, &mut Coherence)>,
 ) {
     for (transform, mut coherence) in &mut boids {
         let this_pos = transform.translation.xy();
 
-        let nearby = quadtree.within_distance(this_pos, settings.visual_range);
+        let nearby = arena.within_distance(&quadtree, this_pos, settings.visual_range);
         let count = nearby.len();
         coherence.effect = if count > 1 {
             #[allow(clippy::cast_precision_loss)]
@@ -174,7 +190,7 @@ fn coherence(
     }
 }
 
-fn coherence_apply(mut boids: Query<(&mut Velocity, &Coherence)>) {
+fn coherence_apply(mut boids: Query<(&mut Velocity, &Coherence), Without<Stunned>>) {
     for (mut vel, coherence) in &mut boids {
         vel.0 += coherence.effect;
     }
@@ -188,6 +204,7 @@ struct Separation {
 
 fn separation(
     settings: Res<BoidSettings>,
+    arena: Res<Arena>,
     quadtree: Res<KDTree2<Tracked>>,
     mut boids: Query<(Entity, &Transform, &mut Separation)>,
 ) {
@@ -195,8 +212,8 @@ fn separation(
         let this_pos = transform.translation.xy();
         let mut c = Vec2::ZERO;
 
-        for (other_pos, _entity) in quadtree
-            .within_distance(this_pos, settings.avoid_range)
+        for (other_pos, _entity) in arena
+            .within_distance(&quadtree, this_pos, settings.avoid_range)
             .into_iter()
             .filter(|(_pos, entity)| {
                 entity
@@ -211,7 +228,7 @@ fn separation(
     }
 }
 
-fn separation_apply(mut boids: Query<(&mut Velocity, &Separation)>) {
+fn separation_apply(mut boids: Query<(&mut Velocity, &Separation), Without<Stunned>>) {
     for (mut vel, separtion) in &mut boids {
         vel.0 += separtion.effect;
     }
@@ -225,6 +242,7 @@ pub struct Alignment {
 
 fn alignment(
     settings: Res<BoidSettings>,
+    arena: Res<Arena>,
     quadtree: Res<KDTree2<Tracked>>,
     mut boids: Query<(&Transform, &Velocity, &mut Alignment)>,
     other: Query<&Velocity, With<Alignment>>,
@@ -233,8 +251,8 @@ fn alignment(
         let this_pos = transform.translation.xy();
         let mut velocities = -vel.0;
         let mut count = -1;
-        for vel in quadtree
-            .within_distance(this_pos, settings.visual_range)
+        for vel in arena
+            .within_distance(&quadtree, this_pos, settings.visual_range)
             .into_iter()
             .filter_map(|(_, entity)| entity)
             .filter_map(|entity| other.get(entity).ok())
@@ -253,7 +271,7 @@ fn alignment(
     }
 }
 
-fn alignment_apply(mut boids: Query<(&mut Velocity, &Alignment)>) {
+fn alignment_apply(mut boids: Query<(&mut Velocity, &Alignment), Without<Stunned>>) {
     for (mut vel, alignment) in &mut boids {
         vel.0 += alignment.effect;
     }
@@ -262,6 +280,8 @@ fn alignment_apply(mut boids: Query<(&mut Velocity, &Alignment)>) {
 #[derive(Component, Default)]
 struct Home<T: Component + Default> {
     influence: f32,
+    /// Steer for the closest target in range only, instead of every target at once.
+    nearest: bool,
     _target: PhantomData<T>,
 }
 
@@ -272,23 +292,41 @@ impl<T: Component + Default> Home<T> {
             ..default()
         }
     }
+
+    pub fn nearest(mut self) -> Self {
+        self.nearest = true;
+        self
+    }
 }
 
 fn home<T: Component + Default>(
     settings: Res<BoidSettings>,
+    arena: Res<Arena>,
     quadtree: Res<KDTree2<Tracked>>,
-    mut homing: Query<(&Transform, &mut Velocity, &Home<T>)>,
+    mut homing: Query<(&Transform, &mut Velocity, &Home<T>), Without<Stunned>>,
     other: Query<&T>,
 ) {
     for (transform, mut vel, home) in &mut homing {
         let this_pos = transform.translation.xy();
-        let mut effect = Vec2::ZERO;
-        for target_pos in quadtree
-            .within_distance(this_pos, settings.home_range)
+        let targets = arena
+            .within_distance(&quadtree, this_pos, settings.home_range)
             .into_iter()
             .filter_map(|(pos, entity)| entity.map(|entity| (pos, entity)))
-            .filter_map(|(pos, entity)| other.get(entity).map(|_| pos).ok())
-        {
+            .filter_map(|(pos, entity)| other.get(entity).map(|_| pos).ok());
+        let targets: Vec<Vec2> = if home.nearest {
+            targets
+                .min_by(|a, b| {
+                    a.distance_squared(this_pos)
+                        .total_cmp(&b.distance_squared(this_pos))
+                })
+                .into_iter()
+                .collect()
+        } else {
+            targets.collect()
+        };
+
+        let mut effect = Vec2::ZERO;
+        for target_pos in targets {
             let dir = (target_pos - this_pos).normalize_or_zero();
             effect += dir * home.influence;
         }
@@ -297,17 +335,25 @@ fn home<T: Component + Default>(
     }
 }
 
+fn difficulty(
+    difficulty: Res<Difficulty>,
+    mut angry: Query<&mut Home<Player>>,
+    mut calm: Query<&mut Home<Collectible>>,
+) {
+    for mut home in &mut angry {
+        home.influence = difficulty.angry_homing;
+    }
+    for mut home in &mut calm {
+        home.influence = difficulty.calm_homing;
+    }
+}
+
 fn update(
     settings: Res<BoidSettings>,
     mut boids: Query<(&mut Transform, &mut Velocity), With<Boid>>,
 ) {
     for (mut transform, mut vel) in &mut boids {
-        if settings.bounds.contains(transform.translation.xy()) {
-            vel.0 = vel.clamp_length_max(settings.max_speed);
-        } else {
-            vel.0 += -transform.translation.xy().normalize_or_zero() * settings.centering_force;
-            vel.0 = vel.clamp_length_max(settings.max_speed * 3.);
-        }
+        vel.0 = vel.clamp_length_max(settings.top_speed());
         transform.rotation = Quat::from_axis_angle(Vec3::Z, vel.0.y.atan2(vel.0.x) + PI * 1.5);
     }
 }
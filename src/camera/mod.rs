@@ -0,0 +1,215 @@
+mod shake;
+
+use bevy::{prelude::*, render::camera::ScalingMode, transform::TransformSystem};
+use interpolation::Lerp;
+
+use crate::{
+    arena::Arena,
+    assets::Images,
+    boid::BoidSettings,
+    player::{Boost, Player},
+    velocity::Velocity,
+};
+
+pub use shake::ShakeSettings;
+
+const TILES: (i32, i32) = (7, 5);
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)));
+        app.insert_resource(CameraSettings {
+            view_height: 720.0,
+            follow: 4.0,
+            look_ahead: 0.4,
+            max_look_ahead: 200.0,
+            clamp: true,
+            zoom: true,
+            max_zoom: 1.4,
+            frame_margin: 160.0,
+            max_frame_zoom: 2.5,
+            tile_size: 512.0,
+            parallax: 0.5,
+        });
+        app.add_systems(Startup, spawn);
+        app.add_systems(
+            PostUpdate,
+            (follow, zoom, background)
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+        );
+        app.add_plugins(shake::Plugin);
+    }
+}
+
+#[derive(Component)]
+pub struct PlayerCamera;
+
+#[derive(Resource)]
+pub struct CameraSettings {
+    pub view_height: f32,
+    pub follow: f32,
+    /// Seconds of velocity the camera leads the player by.
+    pub look_ahead: f32,
+    pub max_look_ahead: f32,
+    pub clamp: bool,
+    pub zoom: bool,
+    pub max_zoom: f32,
+    pub frame_margin: f32,
+    pub max_frame_zoom: f32,
+    pub tile_size: f32,
+    /// 0 keeps the background fixed in the world, 1 pins it to the camera.
+    pub parallax: f32,
+}
+
+#[derive(Component, Default)]
+pub struct Focus(Vec2);
+
+#[derive(Component)]
+struct Tile(IVec2);
+
+fn spawn(mut commands: Commands, settings: Res<CameraSettings>, assets: Res<Images>) {
+    let mut entity = commands.spawn_empty();
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = ScalingMode::FixedVertical(settings.view_height);
+    entity.insert(camera);
+    entity.insert(PlayerCamera);
+    entity.insert(Focus::default());
+    entity.insert(shake::Trauma::default());
+
+    for x in 0..TILES.0 {
+        for y in 0..TILES.1 {
+            let mut tile = commands.spawn_empty();
+            tile.insert(Name::new("Background"));
+            tile.insert(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(settings.tile_size)),
+                    ..default()
+                },
+                texture: assets.background.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, -10.0),
+                ..default()
+            });
+            tile.insert(Tile(IVec2::new(x - TILES.0 / 2, y - TILES.1 / 2)));
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn follow(
+    settings: Res<CameraSettings>,
+    arena: Res<Arena>,
+    players: Query<(&Transform, &Velocity), With<Player>>,
+    mut camera: Query<
+        (&mut Transform, &mut Focus, &OrthographicProjection, &Camera),
+        (With<PlayerCamera>, Without<Player>),
+    >,
+    time: Res<Time>,
+) {
+    let Ok((mut transform, mut focus, projection, camera)) = camera.get_single_mut() else {
+        return;
+    };
+
+    // Look at the middle of everyone's lead point.
+    let mut target = frame(players.iter().map(|(player, vel)| {
+        let lead = (vel.0 * settings.look_ahead).clamp_length_max(settings.max_look_ahead);
+        player.translation.xy() + lead
+    }))
+    .map_or(arena.center(), |frame| frame.center());
+
+    if settings.clamp {
+        let aspect = aspect(camera);
+        let half_view = Vec2::new(aspect, 1.0) * settings.view_height * projection.scale * 0.5;
+        let bounds = arena.bounds();
+        for axis in 0..2 {
+            let (min, max) = (
+                bounds.min[axis] + half_view[axis],
+                bounds.max[axis] - half_view[axis],
+            );
+            // An arena smaller than the view has nothing to hide, so keep following.
+            if min <= max {
+                target[axis] = target[axis].clamp(min, max);
+            }
+        }
+    }
+
+    let blend = 1.0 - (-settings.follow * time.delta_seconds()).exp();
+    focus.0 = focus.0.lerp(target, blend);
+    transform.translation = focus.0.extend(transform.translation.z);
+}
+
+fn frame(points: impl Iterator<Item = Vec2>) -> Option<Rect> {
+    points.fold(None, |frame: Option<Rect>, point| {
+        Some(frame.map_or(Rect::from_corners(point, point), |frame| {
+            frame.union_point(point)
+        }))
+    })
+}
+
+fn aspect(camera: &Camera) -> f32 {
+    camera
+        .logical_viewport_size()
+        .map_or(16.0 / 9.0, |size| size.x / size.y.max(1.0))
+}
+
+fn zoom(
+    settings: Res<CameraSettings>,
+    boid_settings: Res<BoidSettings>,
+    players: Query<(&Transform, &Velocity, &Boost), With<Player>>,
+    mut camera: Query<(&mut OrthographicProjection, &Camera), With<PlayerCamera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut projection, camera)) = camera.get_single_mut() else {
+        return;
+    };
+
+    let speed = if settings.zoom {
+        players
+            .iter()
+            .map(|(_, vel, boost)| {
+                let cruise = boid_settings.top_speed();
+                let top = cruise * boost.multiplier();
+                let ratio = ((vel.length() - cruise) / (top - cruise).max(1.0)).clamp(0.0, 1.0);
+                1.0.lerp(&settings.max_zoom, &ratio)
+            })
+            .fold(1.0, f32::max)
+    } else {
+        1.0
+    };
+
+    // Zoom out far enough to keep every player on screen.
+    let framing = frame(
+        players
+            .iter()
+            .map(|(transform, ..)| transform.translation.xy()),
+    )
+    .map_or(1.0, |frame| {
+        let size = frame.size() + settings.frame_margin * 2.0;
+        let view = Vec2::new(aspect(camera), 1.0) * settings.view_height;
+        (size / view).max_element().min(settings.max_frame_zoom)
+    });
+    let target = speed.max(framing);
+
+    let blend = (time.delta_seconds() * 2.0).min(1.0);
+    projection.scale = projection.scale.lerp(&target, &blend);
+}
+
+fn background(
+    settings: Res<CameraSettings>,
+    camera: Query<&Transform, (With<PlayerCamera>, Without<Tile>)>,
+    mut tiles: Query<(&mut Transform, &Tile)>,
+) {
+    let Ok(camera) = camera.get_single() else {
+        return;
+    };
+
+    let view = camera.translation.xy();
+    let shift = view * settings.parallax;
+    let origin = shift + ((view - shift) / settings.tile_size).round() * settings.tile_size;
+    for (mut transform, tile) in &mut tiles {
+        let position = origin + tile.0.as_vec2() * settings.tile_size;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
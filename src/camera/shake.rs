@@ -0,0 +1,168 @@
+use bevy::{prelude::*, transform::TransformSystem, utils::HashSet};
+
+use crate::{
+    health,
+    input::InputEvent,
+    player::{Boost, Player},
+    shockwave,
+};
+
+use super::{Focus, PlayerCamera};
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ShakeSettings {
+            enabled: true,
+            max_offset: 24.0,
+            max_angle: 0.05,
+            frequency: 25.0,
+            decay: 1.5,
+            range: 1200.0,
+            shockwave: 0.6,
+            boost: 0.25,
+            damage: 0.4,
+        });
+        app.add_systems(
+            Update,
+            (
+                shockwave_trauma.run_if(on_event::<shockwave::Event>()),
+                health_trauma.run_if(on_event::<health::Event>()),
+                boost_trauma,
+                toggle.run_if(on_event::<InputEvent>()),
+            ),
+        );
+        app.add_systems(
+            PostUpdate,
+            shake
+                .after(super::follow)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct ShakeSettings {
+    /// Accessibility toggle; when off, trauma still builds but is never shown.
+    pub enabled: bool,
+    pub max_offset: f32,
+    pub max_angle: f32,
+    pub frequency: f32,
+    pub decay: f32,
+    pub range: f32,
+    pub shockwave: f32,
+    pub boost: f32,
+    pub damage: f32,
+}
+
+/// Shake strength in `0..=1`; the visible shake grows with its square.
+#[derive(Component, Default)]
+pub struct Trauma(pub f32);
+
+impl ShakeSettings {
+    fn falloff(&self, camera: Vec2, position: Vec2) -> f32 {
+        (1.0 - camera.distance(position) / self.range).clamp(0.0, 1.0)
+    }
+}
+
+fn add(trauma: &mut Trauma, amount: f32) {
+    trauma.0 = (trauma.0 + amount).min(1.0);
+}
+
+fn shockwave_trauma(
+    settings: Res<ShakeSettings>,
+    mut camera: Query<(&Focus, &mut Trauma), With<PlayerCamera>>,
+    mut events: EventReader<shockwave::Event>,
+) {
+    let Ok((focus, mut trauma)) = camera.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let shockwave::Event::Spawn {
+            position, radius, ..
+        } = event;
+        let amount = settings.shockwave * radius / 1000.0;
+        add(&mut trauma, amount * settings.falloff(focus.0, *position));
+    }
+}
+
+fn health_trauma(
+    settings: Res<ShakeSettings>,
+    player: Query<&Transform, With<Player>>,
+    mut camera: Query<(&Focus, &mut Trauma), With<PlayerCamera>>,
+    mut events: EventReader<health::Event>,
+) {
+    let Ok((focus, mut trauma)) = camera.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let health::Event::Damaged { target, amount, .. } = event else {
+            continue;
+        };
+        let Ok(transform) = player.get(*target) else {
+            continue;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let amount = settings.damage * *amount as f32;
+        add(
+            &mut trauma,
+            amount * settings.falloff(focus.0, transform.translation.xy()),
+        );
+    }
+}
+
+fn boost_trauma(
+    settings: Res<ShakeSettings>,
+    players: Query<(Entity, &Transform, &Boost), With<Player>>,
+    mut camera: Query<(&Focus, &mut Trauma), With<PlayerCamera>>,
+    mut boosting: Local<HashSet<Entity>>,
+) {
+    let Ok((focus, mut trauma)) = camera.get_single_mut() else {
+        return;
+    };
+
+    for (entity, transform, boost) in &players {
+        if !boost.active() {
+            boosting.remove(&entity);
+        } else if boosting.insert(entity) {
+            add(
+                &mut trauma,
+                settings.boost * settings.falloff(focus.0, transform.translation.xy()),
+            );
+        }
+    }
+}
+
+fn toggle(mut settings: ResMut<ShakeSettings>, mut input: EventReader<InputEvent>) {
+    for input in input.read() {
+        if let InputEvent::ToggleShake = input {
+            settings.enabled = !settings.enabled;
+        }
+    }
+}
+
+fn shake(
+    settings: Res<ShakeSettings>,
+    mut camera: Query<(&mut Transform, &Focus, &mut Trauma), With<PlayerCamera>>,
+    time: Res<Time<Real>>,
+) {
+    let Ok((mut transform, focus, mut trauma)) = camera.get_single_mut() else {
+        return;
+    };
+
+    trauma.0 = (trauma.0 - settings.decay * time.delta_seconds()).max(0.0);
+    let strength = if settings.enabled {
+        trauma.0 * trauma.0
+    } else {
+        0.0
+    };
+
+    let t = time.elapsed_seconds() * settings.frequency;
+    let noise = |seed: f32| (t + seed).sin() * (t * 0.37 + seed * 2.1).sin();
+    let offset = Vec2::new(noise(0.0), noise(17.0)) * settings.max_offset * strength;
+    transform.translation = (focus.0 + offset).extend(transform.translation.z);
+    transform.rotation = Quat::from_rotation_z(noise(42.0) * settings.max_angle * strength);
+}
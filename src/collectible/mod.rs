@@ -0,0 +1,104 @@
+mod spawner;
+
+use bevy::prelude::*;
+
+use crate::{
+    arena::Arena,
+    audio::{self, SoundGroup},
+    powerup::PowerUp,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.add_plugins(spawner::Plugin);
+        app.add_systems(PostUpdate, events.run_if(on_event::<Event>()));
+        app.add_systems(Update, cooldown);
+        app.add_systems(Update, lifetime.run_if(in_state(crate::GameState::Playing)));
+        app.add_systems(OnExit(crate::GameState::GameOver), reset);
+        app.add_systems(Update, contain.run_if(resource_changed::<Arena>()));
+    }
+}
+
+#[derive(Copy, Clone, Debug, Event)]
+pub enum Event {
+    Spawn(CollectibleKind),
+    Collect(Entity),
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CollectibleKind {
+    #[default]
+    Points,
+    PowerUp(PowerUp),
+}
+
+#[derive(Component, Default)]
+pub struct Collectible {
+    pub value: u32,
+    pub heal: u32,
+    pub kind: CollectibleKind,
+}
+
+#[derive(Component)]
+pub struct Lifetime(pub f32);
+
+fn events(
+    mut commands: Commands,
+    mut reader: EventReader<Event>,
+    mut audio_events: EventWriter<audio::Event>,
+    collectibles: Query<&Transform, With<Collectible>>,
+) {
+    for event in reader.read() {
+        if let Event::Collect(entity) = event {
+            if let Ok(trans) = collectibles.get(*entity) {
+                audio_events.send(audio::Event::Play {
+                    group: SoundGroup::Collect,
+                    position: trans.translation.xy(),
+                });
+                commands.entity(*entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Cooldown(f32);
+
+fn cooldown(mut commands: Commands, mut cooldown: Query<(Entity, &mut Cooldown)>, time: Res<Time>) {
+    for (entity, mut cooldown) in &mut cooldown {
+        cooldown.0 -= time.delta_seconds();
+        if cooldown.0 < 0.0 {
+            commands.entity(entity).remove::<Cooldown>();
+        }
+    }
+}
+
+fn lifetime(
+    mut commands: Commands,
+    mut collectibles: Query<(Entity, &mut Lifetime)>,
+    time: Res<Time>,
+) {
+    for (entity, mut lifetime) in &mut collectibles {
+        lifetime.0 -= time.delta_seconds();
+        if lifetime.0 <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn contain(arena: Res<Arena>, mut collectibles: Query<&mut Transform, With<Collectible>>) {
+    for mut transform in &mut collectibles {
+        if !arena.contains(transform.translation.xy()) {
+            transform.translation = arena.clamp(transform.translation.xy()).extend(0.0);
+        }
+    }
+}
+
+fn reset(mut commands: Commands, collectibles: Query<Entity, With<Collectible>>) {
+    for entity in &collectibles {
+        commands.entity(entity).despawn_recursive();
+    }
+}
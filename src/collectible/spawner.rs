@@ -0,0 +1,362 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+use bevy_spatial::kdtree::KDTree2;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    arena::{Arena, Placement},
+    assets::Images,
+    boid::AngryBoi,
+    difficulty::Difficulty,
+    hitbox::{Hurtbox, Layer, Shape},
+    indicator::OffscreenIndicator,
+    player::Player,
+    powerup::{PowerUp, PowerUpSettings},
+    rng::RngSource,
+    shockwave,
+    track::Tracked,
+    GameState, Wave,
+};
+
+use super::{Collectible, CollectibleKind, Cooldown, Event, Lifetime};
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CollectibleSettings {
+            count: 3,
+            per_wave: 0.1,
+            max_count: 8,
+            tiers: vec![
+                Tier {
+                    value: 1,
+                    color: Color::GREEN,
+                    lifetime: None,
+                    weight: 10.0,
+                    wave: 0,
+                    placement: Placement::Uniform,
+                    heal: 0,
+                },
+                Tier {
+                    value: 3,
+                    color: Color::YELLOW,
+                    lifetime: Some(10.0),
+                    weight: 3.0,
+                    wave: 3,
+                    placement: Placement::Away(200.0),
+                    heal: 0,
+                },
+                Tier {
+                    value: 10,
+                    color: Color::ORANGE_RED,
+                    lifetime: Some(6.0),
+                    weight: 1.0,
+                    wave: 8,
+                    placement: Placement::Edge,
+                    heal: 1,
+                },
+            ],
+            power_up_placement: Placement::Ring {
+                inner: 150.0,
+                outer: 350.0,
+            },
+            candidates: 16,
+            player_distance: 250.0,
+            threat_range: 100.0,
+            recent: 8,
+            recent_distance: 150.0,
+        });
+        app.init_resource::<RecentSpawns>();
+        app.add_systems(Update, fill.run_if(in_state(GameState::Playing)));
+        app.add_systems(PostUpdate, spawn.run_if(on_event::<Event>()));
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+pub struct Tier {
+    pub value: u32,
+    pub color: Color,
+    pub lifetime: Option<f32>,
+    pub weight: f32,
+    pub wave: u32,
+    pub placement: Placement,
+    pub heal: u32,
+}
+
+#[derive(Resource)]
+pub struct CollectibleSettings {
+    pub count: usize,
+    pub per_wave: f32,
+    pub max_count: usize,
+    pub tiers: Vec<Tier>,
+    /// Power-ups spawn around the player so they can be reached before expiring.
+    pub power_up_placement: Placement,
+    pub candidates: usize,
+    pub player_distance: f32,
+    pub threat_range: f32,
+    pub recent: usize,
+    pub recent_distance: f32,
+}
+
+impl CollectibleSettings {
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn count(&self, wave: u32) -> usize {
+        (self.count + (wave as f32 * self.per_wave) as usize).min(self.max_count)
+    }
+
+    fn tier(&self, wave: u32, rng: &mut impl Rng) -> Option<&Tier> {
+        let unlocked = || self.tiers.iter().filter(|tier| tier.wave <= wave);
+        let mut roll = rng.gen::<f32>() * unlocked().map(|tier| tier.weight).sum::<f32>();
+        unlocked()
+            .find(|tier| {
+                roll -= tier.weight;
+                roll <= 0.0
+            })
+            .or_else(|| unlocked().next_back())
+    }
+}
+
+#[derive(Resource, Default)]
+struct RecentSpawns(VecDeque<Vec2>);
+
+fn fill(
+    settings: Res<CollectibleSettings>,
+    wave: Res<Wave>,
+    collectibles: Query<&Collectible>,
+    mut events: EventWriter<Event>,
+) {
+    let present = collectibles
+        .iter()
+        .filter(|collectible| collectible.kind == CollectibleKind::Points)
+        .count();
+    for _ in present..settings.count(wave.0) {
+        events.send(Event::Spawn(CollectibleKind::Points));
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+fn spawn(
+    mut commands: Commands,
+    images: Res<Images>,
+    settings: Res<CollectibleSettings>,
+    power_up_settings: Res<PowerUpSettings>,
+    difficulty: Res<Difficulty>,
+    wave: Res<Wave>,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    players: Query<&Transform, With<Player>>,
+    angry: Query<(), With<AngryBoi>>,
+    mut recent: ResMut<RecentSpawns>,
+    mut rng: ResMut<RngSource>,
+    mut reader: EventReader<Event>,
+    mut shockwave_events: EventWriter<shockwave::Event>,
+) {
+    let players = players
+        .iter()
+        .map(|transform| transform.translation.xy())
+        .collect::<Vec<_>>();
+
+    for event in reader.read() {
+        let Event::Spawn(kind) = event else {
+            continue;
+        };
+
+        let (value, heal, color, lifetime, placement) = match kind {
+            CollectibleKind::Points => {
+                let Some(tier) = settings.tier(wave.0, &mut **rng) else {
+                    continue;
+                };
+                (
+                    tier.value,
+                    tier.heal,
+                    tier.color,
+                    tier.lifetime,
+                    tier.placement,
+                )
+            }
+            CollectibleKind::PowerUp(power_up) => (
+                0,
+                0,
+                power_up.color(),
+                Some(power_up_settings.lifetime),
+                settings.power_up_placement,
+            ),
+        };
+
+        let score = |pos: Vec2| {
+            let player = players
+                .iter()
+                .map(|player| (pos.distance(*player) / settings.player_distance).min(1.0))
+                .fold(1.0, f32::min);
+            let recent = recent
+                .0
+                .iter()
+                .map(|spot| (pos.distance(*spot) / settings.recent_distance).min(1.0))
+                .fold(1.0, f32::min);
+            #[allow(clippy::cast_precision_loss)]
+            let threats = arena
+                .within_distance(&quadtree, pos, settings.threat_range)
+                .into_iter()
+                .filter_map(|(_pos, entity)| entity)
+                .filter(|entity| angry.contains(*entity))
+                .count() as f32;
+            player + recent - threats
+        };
+        let origin = players
+            .choose(&mut **rng)
+            .copied()
+            .unwrap_or_else(|| arena.center());
+        let pos = (0..settings.candidates.max(1))
+            .map(|_| arena.sample(placement, origin, &mut **rng))
+            .map(|pos| (pos, score(pos)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(pos, _)| pos)
+            .unwrap_or_default();
+
+        recent.0.push_front(pos);
+        recent.0.truncate(settings.recent);
+
+        let mut entity = commands.spawn_empty();
+        entity.insert(Name::new("Collectible"));
+        entity.insert(SpriteBundle {
+            sprite: Sprite { color, ..default() },
+            texture: images.collectible.clone(),
+            ..default()
+        });
+        entity.insert(Tracked);
+        entity.insert(Collectible {
+            value,
+            heal,
+            kind: *kind,
+        });
+        if let Some(lifetime) = lifetime {
+            entity.insert(Lifetime(lifetime));
+        }
+        let mut indicator = OffscreenIndicator::new(color);
+        if let CollectibleKind::PowerUp(power_up) = kind {
+            indicator = indicator.with_icon(power_up.icon());
+            entity.with_children(|parent| {
+                parent.spawn(icon(*power_up));
+            });
+        }
+        entity.insert(indicator);
+        entity.insert(Hurtbox {
+            shape: Shape::Rect(Vec2::splat(14.0)),
+            layer: Layer::Collectible,
+        });
+        entity.insert(TransformBundle {
+            local: Transform::from_translation(pos.extend(0.0)),
+            ..default()
+        });
+        entity.insert(Cooldown(difficulty.collectible_cooldown));
+
+        if let CollectibleKind::Points = kind {
+            shockwave_events.send(shockwave::Event::Spawn {
+                position: pos,
+                radius: 100.,
+                duration: Duration::from_secs_f32(1.),
+                color: Color::GRAY,
+                effect: shockwave::Effect::Repel,
+                falloff: shockwave::Falloff::Linear,
+                affects_player: false,
+                affects_collectibles: false,
+                source: None,
+            });
+        }
+    }
+}
+
+fn icon(power_up: PowerUp) -> Text2dBundle {
+    Text2dBundle {
+        text: Text::from_section(
+            power_up.icon(),
+            TextStyle {
+                font_size: 16.0,
+                color: Color::BLACK,
+                ..default()
+            },
+        ),
+        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+        ..default()
+    }
+}
+
+fn reset(mut recent: ResMut<RecentSpawns>) {
+    recent.0.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn tier(value: u32, weight: f32, wave: u32) -> Tier {
+        Tier {
+            value,
+            color: Color::WHITE,
+            lifetime: None,
+            weight,
+            wave,
+            placement: Placement::Uniform,
+            heal: 0,
+        }
+    }
+
+    fn settings(tiers: Vec<Tier>) -> CollectibleSettings {
+        CollectibleSettings {
+            count: 3,
+            per_wave: 0.5,
+            max_count: 5,
+            tiers,
+            power_up_placement: Placement::Uniform,
+            candidates: 1,
+            player_distance: 0.0,
+            threat_range: 0.0,
+            recent: 0,
+            recent_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn tiers_unlock_by_wave() {
+        let tiered = settings(vec![tier(1, 1.0, 0), tier(3, 1.0, 3), tier(10, 1.0, 8)]);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let values = |wave, rng: &mut ChaCha8Rng| {
+            let mut values = (0..200)
+                .map(|_| tiered.tier(wave, rng).unwrap().value)
+                .collect::<Vec<_>>();
+            values.sort_unstable();
+            values.dedup();
+            values
+        };
+        assert_eq!(values(0, &mut rng), [1]);
+        assert_eq!(values(3, &mut rng), [1, 3]);
+        assert_eq!(values(8, &mut rng), [1, 3, 10]);
+
+        let locked = settings(vec![tier(3, 1.0, 3)]);
+        assert!(locked.tier(0, &mut rng).is_none());
+    }
+
+    #[test]
+    fn tiers_follow_weights() {
+        let settings = settings(vec![tier(1, 3.0, 0), tier(3, 1.0, 0), tier(10, 0.0, 0)]);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let rare = (0..4000)
+            .filter(|_| settings.tier(0, &mut rng).unwrap().value == 3)
+            .count();
+        assert!((900..1100).contains(&rare), "{rare}");
+    }
+
+    #[test]
+    fn count_grows_to_max() {
+        let settings = settings(Vec::new());
+        assert_eq!(settings.count(0), 3);
+        assert_eq!(settings.count(3), 4);
+        assert_eq!(settings.count(100), 5);
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    arena::{ArenaSettings, Level},
+    boid::BoidSettings,
+    difficulty::{DifficultySettings, Preset},
+    mode::{GameMode, HighScores},
+    rng::RngSource,
+    GameState, WaveScript, WaveSpawn,
+};
+
+/// Keeps the modifier rolls independent of the gameplay stream sharing the seed.
+const MODIFIER_SALT: u64 = 0x00da_11c5;
+const SCRIPTED_WAVES: usize = 5;
+/// Everyone plays the daily challenge on the same difficulty.
+const DAILY_PRESET: Preset = Preset::Normal;
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DailyChallenge>();
+        app.add_systems(OnExit(GameState::GameOver), apply);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        #[allow(clippy::cast_possible_wrap)]
+        Self::from_days((unix_seconds() / 86_400) as i64)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        Some(Self {
+            year: parts.next()?.parse().ok()?,
+            month: parts.next()?.parse().ok()?,
+            day: parts.next()?.parse().ok()?,
+        })
+    }
+
+    fn seed(self) -> u64 {
+        #[allow(clippy::cast_sign_loss)]
+        let year = self.year as u64;
+        year * 10_000 + u64::from(self.month) * 100 + u64::from(self.day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// `SystemTime::now` panics on the web.
+#[cfg(target_arch = "wasm32")]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn unix_seconds() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[derive(Resource, Default)]
+pub struct DailyChallenge {
+    pub date: Option<Date>,
+    /// False once today's attempt is on record; later runs are practice.
+    pub scored: bool,
+}
+
+const BOID_PRESETS: [(f32, f32, f32); 3] = [
+    // Tight, drifting clumps.
+    (0.35, 0.3, 0.3),
+    // Loose, fast-turning swarm.
+    (0.1, 0.7, 0.2),
+    // Long, aligned streams.
+    (0.15, 0.45, 0.8),
+];
+
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    mode: Res<GameMode>,
+    mut scores: ResMut<HighScores>,
+    mut challenge: ResMut<DailyChallenge>,
+    mut rng: ResMut<RngSource>,
+    mut arena: ResMut<ArenaSettings>,
+    mut boids: ResMut<BoidSettings>,
+    mut script: ResMut<WaveScript>,
+    mut difficulty: ResMut<DifficultySettings>,
+    mut restore: Local<Option<Preset>>,
+) {
+    if *mode != GameMode::Daily {
+        // Only undo our own changes, so tweaks made outside daily runs survive restarts.
+        if let Some(preset) = restore.take() {
+            *arena = ArenaSettings::default();
+            *boids = BoidSettings::default();
+            *script = WaveScript::default();
+            *difficulty = DifficultySettings::from(preset);
+        }
+        return;
+    }
+    if restore.is_none() {
+        *restore = Some(difficulty.preset);
+    }
+    if difficulty.preset != DAILY_PRESET {
+        *difficulty = DifficultySettings::from(DAILY_PRESET);
+    }
+
+    let date = Date::today();
+    challenge.date = Some(date);
+    // The attempt counts from the start, so quitting early does not earn a retry.
+    challenge.scored = !scores.played_daily(date);
+    if challenge.scored {
+        scores.mark_daily(date);
+    }
+    *rng = RngSource::seeded(date.seed());
+
+    let mut modifiers = ChaCha8Rng::seed_from_u64(date.seed() ^ MODIFIER_SALT);
+    let levels = ArenaSettings::default().levels;
+    if let Some(level) = levels.choose(&mut modifiers) {
+        arena.levels = vec![Level {
+            wave: 0,
+            ..level.clone()
+        }];
+    }
+
+    let (coherence, separation, alignment) =
+        BOID_PRESETS[modifiers.gen_range(0..BOID_PRESETS.len())];
+    *boids = BoidSettings {
+        coherence,
+        separation,
+        alignment,
+        ..default()
+    };
+
+    script.0 = (0..SCRIPTED_WAVES)
+        .map(|_| WaveSpawn {
+            bois: modifiers.gen_range(20..=60),
+            calm_bois: modifiers.gen_range(5..=15),
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+
+    #[test]
+    fn from_days() {
+        let date = |year, month, day| Date { year, month, day };
+        assert_eq!(Date::from_days(0), date(1970, 1, 1));
+        assert_eq!(Date::from_days(11_016), date(2000, 2, 29));
+        assert_eq!(Date::from_days(20_744), date(2026, 10, 18));
+    }
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use interpolation::Lerp;
+
+use crate::{
+    boid::BoidSettings, input::InputEvent, mode::GameMode, points::Points, GameState, Wave,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DifficultySettings::from(Preset::Normal));
+        app.insert_resource(Difficulty::at(
+            &DifficultySettings::from(Preset::Normal),
+            0.0,
+        ));
+        app.add_systems(Update, update);
+        app.add_systems(
+            Update,
+            cycle
+                .run_if(in_state(GameState::Paused))
+                .run_if(on_event::<InputEvent>()),
+        );
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Preset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Preset {
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Easy => "easy",
+            Preset::Normal => "normal",
+            Preset::Hard => "hard",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Preset::Easy => Preset::Normal,
+            Preset::Normal => Preset::Hard,
+            Preset::Hard => Preset::Easy,
+        }
+    }
+}
+
+/// Eases from `start` to `end` as the difficulty level climbs to `span`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Curve {
+    pub start: f32,
+    pub end: f32,
+    pub span: f32,
+}
+
+impl Curve {
+    fn new(start: f32, end: f32, span: f32) -> Self {
+        Self { start, end, span }
+    }
+
+    pub fn at(&self, level: f32) -> f32 {
+        let t = (level / self.span.max(f32::EPSILON)).clamp(0.0, 1.0);
+        self.start.lerp(&self.end, &t)
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct DifficultySettings {
+    pub preset: Preset,
+    pub per_wave: f32,
+    pub per_minute: f32,
+    pub per_kilopoint: f32,
+    pub angry_count: Curve,
+    pub angry_homing: Curve,
+    /// Multiplies `BoidSettings::max_speed`.
+    pub speed_scale: Curve,
+    pub collectible_cooldown: Curve,
+    pub calm_homing: Curve,
+}
+
+impl From<Preset> for DifficultySettings {
+    fn from(preset: Preset) -> Self {
+        let normal = Self {
+            preset,
+            per_wave: 1.0,
+            per_minute: 2.0,
+            per_kilopoint: 1.0,
+            angry_count: Curve::new(1.0, 3.0, 40.0),
+            angry_homing: Curve::new(10.0, 20.0, 40.0),
+            speed_scale: Curve::new(1.0, 1.4, 40.0),
+            collectible_cooldown: Curve::new(0.5, 1.5, 40.0),
+            calm_homing: Curve::new(3.0, 6.0, 40.0),
+        };
+        match preset {
+            Preset::Easy => Self {
+                per_minute: 1.0,
+                angry_count: Curve::new(1.0, 2.0, 60.0),
+                angry_homing: Curve::new(6.0, 14.0, 60.0),
+                speed_scale: Curve::new(0.9, 1.15, 60.0),
+                collectible_cooldown: Curve::new(0.3, 1.0, 60.0),
+                calm_homing: Curve::new(2.0, 4.0, 60.0),
+                ..normal
+            },
+            Preset::Normal => normal,
+            Preset::Hard => Self {
+                per_minute: 3.0,
+                per_kilopoint: 2.0,
+                angry_count: Curve::new(2.0, 5.0, 30.0),
+                angry_homing: Curve::new(14.0, 30.0, 30.0),
+                speed_scale: Curve::new(1.1, 1.65, 30.0),
+                collectible_cooldown: Curve::new(0.8, 2.0, 30.0),
+                calm_homing: Curve::new(4.0, 8.0, 30.0),
+                ..normal
+            },
+        }
+    }
+}
+
+#[derive(Resource, Debug, PartialEq)]
+pub struct Difficulty {
+    pub elapsed: f32,
+    pub level: f32,
+    pub angry_count: u32,
+    pub angry_homing: f32,
+    pub speed_scale: f32,
+    pub collectible_cooldown: f32,
+    pub calm_homing: f32,
+}
+
+impl Difficulty {
+    fn at(settings: &DifficultySettings, level: f32) -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let angry_count = settings.angry_count.at(level).round() as u32;
+        Self {
+            elapsed: 0.0,
+            level,
+            angry_count,
+            angry_homing: settings.angry_homing.at(level),
+            speed_scale: settings.speed_scale.at(level),
+            collectible_cooldown: settings.collectible_cooldown.at(level),
+            calm_homing: settings.calm_homing.at(level),
+        }
+    }
+}
+
+fn update(
+    settings: Res<DifficultySettings>,
+    state: Res<State<GameState>>,
+    wave: Res<Wave>,
+    points: Res<Points>,
+    mut difficulty: ResMut<Difficulty>,
+    mut boid_settings: ResMut<BoidSettings>,
+    time: Res<Time>,
+) {
+    let mut elapsed = difficulty.elapsed;
+    if *state.get() == GameState::Playing {
+        elapsed += time.delta_seconds();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let level = wave.0 as f32 * settings.per_wave
+        + elapsed / 60.0 * settings.per_minute
+        + points.0 as f32 / 1000.0 * settings.per_kilopoint;
+    difficulty.set_if_neq(Difficulty {
+        elapsed,
+        ..Difficulty::at(&settings, level)
+    });
+
+    #[allow(clippy::float_cmp)]
+    if boid_settings.speed_scale != difficulty.speed_scale {
+        boid_settings.speed_scale = difficulty.speed_scale;
+    }
+}
+
+fn cycle(
+    mode: Res<GameMode>,
+    mut settings: ResMut<DifficultySettings>,
+    mut input: EventReader<InputEvent>,
+) {
+    // Daily runs are played on a fixed preset.
+    if *mode == GameMode::Daily {
+        return;
+    }
+    for input in input.read() {
+        if let InputEvent::CycleDifficulty = input {
+            *settings = DifficultySettings::from(settings.preset.next());
+        }
+    }
+}
+
+fn reset(mut difficulty: ResMut<Difficulty>) {
+    difficulty.elapsed = 0.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn curve() {
+        let curve = Curve::new(1.0, 3.0, 40.0);
+        assert_eq!(curve.at(-5.0), 1.0);
+        assert_eq!(curve.at(0.0), 1.0);
+        assert_eq!(curve.at(20.0), 2.0);
+        assert_eq!(curve.at(40.0), 3.0);
+        assert_eq!(curve.at(100.0), 3.0);
+        // A zero span jumps straight to the end once the level is positive.
+        assert_eq!(Curve::new(1.0, 3.0, 0.0).at(0.5), 3.0);
+    }
+
+    #[test]
+    fn presets() {
+        let settings = DifficultySettings::from(Preset::Normal);
+        assert_eq!(Difficulty::at(&settings, 0.0).angry_count, 1);
+        assert_eq!(Difficulty::at(&settings, 15.0).angry_count, 2);
+        assert_eq!(Difficulty::at(&settings, 40.0).angry_count, 3);
+        assert_eq!(Preset::Hard.next(), Preset::Easy);
+        assert_eq!(DifficultySettings::from(Preset::Easy).preset, Preset::Easy);
+    }
+}
@@ -1,28 +1,87 @@
 use bevy::prelude::*;
-use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
 
-use crate::track::Tracked;
+use crate::{hitbox, GameState};
 
 pub struct Plugin;
 
 impl bevy::prelude::Plugin for Plugin {
     fn build(&self, app: &mut App) {
         app.add_event::<Event>();
-        app.add_systems(Update, invulnerable);
+        app.add_event::<Damage>();
+        app.add_event::<Heal>();
+        app.add_systems(
+            Update,
+            invulnerable.run_if(not(in_state(GameState::Paused))),
+        );
+        app.add_systems(Update, damages.run_if(on_event::<hitbox::Event>()));
+        app.add_systems(
+            PostUpdate,
+            apply.run_if(on_event::<Damage>().or_else(on_event::<Heal>())),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DamageKind {
+    Collision,
+    Hazard,
+    Shockwave,
+}
+
+impl DamageKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            DamageKind::Collision => "collision",
+            DamageKind::Hazard => "the void",
+            DamageKind::Shockwave => "a shockwave",
+        }
     }
 }
 
 #[derive(Debug, Event)]
 pub enum Event {
+    Damaged {
+        target: Entity,
+        attacker: Option<Entity>,
+        amount: u32,
+        kind: DamageKind,
+    },
+    Healed {
+        target: Entity,
+        amount: u32,
+    },
     Die(Entity),
 }
 
+#[derive(Debug, Event)]
+pub struct Damage {
+    pub target: Entity,
+    pub attacker: Option<Entity>,
+    pub amount: u32,
+    pub kind: DamageKind,
+}
+
+#[derive(Debug, Event)]
+pub struct Heal {
+    pub target: Entity,
+    pub amount: u32,
+}
+
 #[derive(Component)]
 pub struct Health(pub u32);
 
+#[derive(Component)]
+pub struct MaxHealth(pub u32);
+
 #[derive(Component)]
 pub struct Invulnerable(pub f32);
 
+#[derive(Component)]
+pub struct Damager {
+    pub amount: u32,
+    pub kind: DamageKind,
+}
+
 fn invulnerable(
     mut commands: Commands,
     mut invul: Query<(Entity, &mut Invulnerable)>,
@@ -36,32 +95,115 @@ fn invulnerable(
     }
 }
 
-pub fn damages<Attacker: Component + Default, Target: Component + Default, const DISTANCE: u32>(
+fn apply(
     mut commands: Commands,
-    quadtree: Res<KDTree2<Tracked>>,
-    attacker: Query<&Transform, With<Attacker>>,
-    mut target: Query<&mut Health, (With<Target>, Without<Invulnerable>)>,
+    mut health: Query<(&mut Health, Option<&MaxHealth>, Has<Invulnerable>)>,
+    mut damage: EventReader<Damage>,
+    mut heal: EventReader<Heal>,
     mut events: EventWriter<Event>,
 ) {
-    for trans in attacker.iter() {
-        let pos = trans.translation.xy();
-        #[allow(clippy::cast_precision_loss)]
-        for entity in quadtree
-            .within_distance(pos, DISTANCE as f32)
-            .into_iter()
-            .filter_map(|(_pos, entity)| entity)
-        {
-            let Ok(mut health) = target.get_mut(entity) else {
-                continue;
-            };
-
-            health.0 = if health.0 > 1 {
-                commands.entity(entity).insert(Invulnerable(1.));
-                health.0 - 1
-            } else {
-                events.send(Event::Die(entity));
-                0
-            };
+    let mut hit = Vec::new();
+    for damage in damage.read() {
+        if hit.contains(&damage.target) {
+            continue;
+        }
+        let Ok((mut health, _max, false)) = health.get_mut(damage.target) else {
+            continue;
+        };
+        if health.0 == 0 {
+            continue;
         }
+        hit.push(damage.target);
+
+        let amount = damage.amount.min(health.0);
+        health.0 -= amount;
+        events.send(Event::Damaged {
+            target: damage.target,
+            attacker: damage.attacker,
+            amount,
+            kind: damage.kind,
+        });
+
+        if health.0 > 0 {
+            commands.entity(damage.target).insert(Invulnerable(1.));
+        } else {
+            events.send(Event::Die(damage.target));
+        }
+    }
+
+    for heal in heal.read() {
+        let Ok((mut health, max, _invulnerable)) = health.get_mut(heal.target) else {
+            continue;
+        };
+        if health.0 == 0 {
+            continue;
+        }
+
+        let healed = max.map_or(health.0 + heal.amount, |max| {
+            (health.0 + heal.amount).min(max.0.max(health.0))
+        });
+        let amount = healed - health.0;
+        health.0 = healed;
+        if amount > 0 {
+            events.send(Event::Healed {
+                target: heal.target,
+                amount,
+            });
+        }
+    }
+}
+
+fn damages(
+    damagers: Query<&Damager>,
+    targets: Query<(), (With<Health>, Without<Invulnerable>)>,
+    mut overlaps: EventReader<hitbox::Event>,
+    mut damage: EventWriter<Damage>,
+) {
+    for event in overlaps.read() {
+        let hitbox::Event::Overlap {
+            hitbox, hurtbox, ..
+        } = event;
+        let Ok(damager) = damagers.get(*hitbox) else {
+            continue;
+        };
+        if !targets.contains(*hurtbox) {
+            continue;
+        }
+
+        damage.send(Damage {
+            target: *hurtbox,
+            attacker: Some(*hitbox),
+            amount: damager.amount,
+            kind: damager.kind,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heals_are_clamped() {
+        let mut app = App::new();
+        app.add_event::<Event>();
+        app.add_event::<Damage>();
+        app.add_event::<Heal>();
+        app.add_systems(Update, apply);
+        let target = app.world.spawn((Health(2), MaxHealth(3))).id();
+
+        app.world.send_event(Heal { target, amount: 5 });
+        app.update();
+
+        assert_eq!(app.world.get::<Health>(target).unwrap().0, 3);
+        let events = app.world.resource::<Events<Event>>();
+        let healed = events
+            .iter_current_update_events()
+            .filter_map(|event| match event {
+                Event::Healed { amount, .. } => Some(*amount),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(healed, [1]);
     }
 }
@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use bevy_spatial::kdtree::KDTree2;
+
+use crate::{arena::Arena, track::Tracked};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.insert_resource(HitboxSettings {
+            pairs: vec![
+                (Layer::Player, Layer::Collectible),
+                (Layer::CalmBoi, Layer::Collectible),
+                (Layer::AngryBoi, Layer::Player),
+            ],
+            gizmos: false,
+        });
+        app.add_systems(Update, detect);
+        app.add_systems(PostUpdate, gizmo.run_if(gizmos_enabled));
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Layer {
+    Player,
+    Collectible,
+    CalmBoi,
+    AngryBoi,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Shape {
+    Circle(f32),
+    Rect(Vec2),
+}
+
+impl Shape {
+    fn scaled(self, scale: Vec3) -> Self {
+        match self {
+            Shape::Circle(radius) => Shape::Circle(radius * scale.x.max(scale.y)),
+            Shape::Rect(half_size) => Shape::Rect(half_size * scale.xy()),
+        }
+    }
+
+    fn bounding_radius(self) -> f32 {
+        match self {
+            Shape::Circle(radius) => radius,
+            Shape::Rect(half_size) => half_size.length(),
+        }
+    }
+
+    fn overlaps(self, position: Vec2, other: Shape, other_position: Vec2) -> bool {
+        let offset = other_position - position;
+        match (self, other) {
+            (Shape::Circle(a), Shape::Circle(b)) => offset.length_squared() <= (a + b) * (a + b),
+            (Shape::Rect(a), Shape::Rect(b)) => {
+                offset.x.abs() <= a.x + b.x && offset.y.abs() <= a.y + b.y
+            }
+            (Shape::Circle(radius), Shape::Rect(half_size)) => {
+                let closest = offset + (-offset).clamp(-half_size, half_size);
+                closest.length_squared() <= radius * radius
+            }
+            (Shape::Rect(_), Shape::Circle(_)) => other.overlaps(other_position, self, position),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Hitbox {
+    pub shape: Shape,
+    pub layer: Layer,
+}
+
+#[derive(Component)]
+pub struct Hurtbox {
+    pub shape: Shape,
+    pub layer: Layer,
+}
+
+/// Scales shapes in place of `Transform::scale` on sprites whose scale is animated.
+#[derive(Component, Copy, Clone)]
+pub struct BaseScale(pub Vec3);
+
+fn scale(transform: &Transform, base: Option<&BaseScale>) -> Vec3 {
+    base.map_or(transform.scale, |base| base.0)
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Overlap {
+        hitbox: Entity,
+        hurtbox: Entity,
+        layer: Layer,
+        target: Layer,
+        position: Vec2,
+    },
+}
+
+#[derive(Resource)]
+pub struct HitboxSettings {
+    pub pairs: Vec<(Layer, Layer)>,
+    pub gizmos: bool,
+}
+
+fn detect(
+    settings: Res<HitboxSettings>,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    hitboxes: Query<(Entity, &Hitbox, &Transform, Option<&BaseScale>)>,
+    hurtboxes: Query<(&Hurtbox, &Transform, Option<&BaseScale>)>,
+    mut events: EventWriter<Event>,
+) {
+    let reach = hurtboxes
+        .iter()
+        .map(|(hurtbox, transform, base)| {
+            hurtbox
+                .shape
+                .scaled(scale(transform, base))
+                .bounding_radius()
+        })
+        .fold(0.0, f32::max);
+
+    for (entity, hitbox, transform, base) in &hitboxes {
+        if !settings
+            .pairs
+            .iter()
+            .any(|(layer, _)| *layer == hitbox.layer)
+        {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        let shape = hitbox.shape.scaled(scale(transform, base));
+        for other in arena
+            .within_distance(&quadtree, position, shape.bounding_radius() + reach)
+            .into_iter()
+            .filter_map(|(_pos, entity)| entity)
+            .filter(|other| *other != entity)
+        {
+            let Ok((hurtbox, other_transform, other_base)) = hurtboxes.get(other) else {
+                continue;
+            };
+            if !settings.pairs.contains(&(hitbox.layer, hurtbox.layer)) {
+                continue;
+            }
+
+            let other_position = position + arena.delta(position, other_transform.translation.xy());
+            let other_shape = hurtbox.shape.scaled(scale(other_transform, other_base));
+            if shape.overlaps(position, other_shape, other_position) {
+                events.send(Event::Overlap {
+                    hitbox: entity,
+                    hurtbox: other,
+                    layer: hitbox.layer,
+                    target: hurtbox.layer,
+                    position: other_position,
+                });
+            }
+        }
+    }
+}
+
+fn gizmos_enabled(settings: Res<HitboxSettings>) -> bool {
+    settings.gizmos
+}
+
+fn gizmo(
+    mut gizmos: Gizmos,
+    hitboxes: Query<(&Hitbox, &Transform, Option<&BaseScale>)>,
+    hurtboxes: Query<(&Hurtbox, &Transform, Option<&BaseScale>)>,
+) {
+    let shapes = hitboxes
+        .iter()
+        .map(|(hitbox, transform, base)| (hitbox.shape, transform, base, Color::RED))
+        .chain(
+            hurtboxes
+                .iter()
+                .map(|(hurtbox, transform, base)| (hurtbox.shape, transform, base, Color::GREEN)),
+        );
+
+    for (shape, transform, base, color) in shapes {
+        let position = transform.translation.xy();
+        match shape.scaled(scale(transform, base)) {
+            Shape::Circle(radius) => {
+                gizmos.circle_2d(position, radius, color);
+            }
+            Shape::Rect(half_size) => {
+                gizmos.rect_2d(position, 0.0, half_size * 2.0, color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlaps() {
+        let circle = Shape::Circle(10.0);
+        let rect = Shape::Rect(Vec2::new(10.0, 5.0));
+
+        assert!(circle.overlaps(Vec2::ZERO, circle, Vec2::new(20.0, 0.0)));
+        assert!(!circle.overlaps(Vec2::ZERO, circle, Vec2::new(20.1, 0.0)));
+        assert!(rect.overlaps(Vec2::ZERO, rect, Vec2::new(20.0, 10.0)));
+        assert!(!rect.overlaps(Vec2::ZERO, rect, Vec2::new(0.0, 10.1)));
+
+        // Near the rectangle's corner, only the true distance counts.
+        let corner = Vec2::new(10.0 + 7.5, 5.0 + 7.5);
+        assert!(!circle.overlaps(corner, rect, Vec2::ZERO));
+        assert!(!rect.overlaps(Vec2::ZERO, circle, corner));
+        assert!(circle.overlaps(Vec2::new(19.0, 0.0), rect, Vec2::ZERO));
+        assert!(rect.overlaps(Vec2::ZERO, circle, Vec2::new(19.0, 0.0)));
+    }
+
+    #[test]
+    fn scaled() {
+        let scale = Vec3::new(2.0, 0.5, 1.0);
+        assert_eq!(Shape::Circle(10.0).scaled(scale), Shape::Circle(20.0));
+        assert_eq!(
+            Shape::Rect(Vec2::new(10.0, 10.0)).scaled(scale),
+            Shape::Rect(Vec2::new(20.0, 5.0))
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::f32::consts::PI;
+
+use bevy::{prelude::*, transform::TransformSystem};
+
+use crate::{assets::Images, camera::PlayerCamera};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(IndicatorSettings {
+            margin: 24.0,
+            size: 16.0,
+        });
+        app.add_systems(Update, attach);
+        app.add_systems(
+            PostUpdate,
+            update
+                .after(crate::camera::follow)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Points at its entity from the edge of the screen while the entity is out of view.
+#[derive(Component, Clone)]
+pub struct OffscreenIndicator {
+    pub color: Color,
+    pub icon: Option<&'static str>,
+    pub distance: bool,
+    pub range: f32,
+}
+
+impl OffscreenIndicator {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            icon: None,
+            distance: true,
+            range: f32::INFINITY,
+        }
+    }
+
+    pub fn with_icon(mut self, icon: &'static str) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_range(mut self, range: f32) -> Self {
+        self.range = range;
+        self
+    }
+}
+
+#[derive(Resource)]
+pub struct IndicatorSettings {
+    pub margin: f32,
+    pub size: f32,
+}
+
+#[derive(Component)]
+struct Indicator(Entity);
+
+#[derive(Component)]
+struct Arrow;
+
+#[derive(Component)]
+struct Label;
+
+fn attach(
+    mut commands: Commands,
+    images: Res<Images>,
+    settings: Res<IndicatorSettings>,
+    targets: Query<(Entity, &OffscreenIndicator), Added<OffscreenIndicator>>,
+) {
+    for (target, indicator) in &targets {
+        let mut entity = commands.spawn_empty();
+        entity.insert(Name::new("Indicator"));
+        entity.insert(Indicator(target));
+        entity.insert(SpatialBundle {
+            visibility: Visibility::Hidden,
+            ..default()
+        });
+        entity.with_children(|parent| {
+            parent.spawn((
+                Arrow,
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: indicator.color,
+                        custom_size: Some(Vec2::splat(settings.size)),
+                        ..default()
+                    },
+                    texture: images.player.clone(),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Label,
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 14.0,
+                            color: indicator.color,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                },
+            ));
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update(
+    mut commands: Commands,
+    settings: Res<IndicatorSettings>,
+    camera: Query<
+        (&Transform, &OrthographicProjection),
+        (
+            With<PlayerCamera>,
+            Without<Indicator>,
+            Without<Arrow>,
+            Without<Label>,
+        ),
+    >,
+    targets: Query<(&Transform, &OffscreenIndicator), Without<Indicator>>,
+    mut indicators: Query<
+        (
+            Entity,
+            &Indicator,
+            &mut Transform,
+            &mut Visibility,
+            &Children,
+        ),
+        Without<PlayerCamera>,
+    >,
+    mut arrows: Query<
+        &mut Transform,
+        (With<Arrow>, Without<Indicator>, Without<OffscreenIndicator>),
+    >,
+    mut labels: Query<
+        (&mut Transform, &mut Text),
+        (
+            With<Label>,
+            Without<Arrow>,
+            Without<Indicator>,
+            Without<OffscreenIndicator>,
+        ),
+    >,
+) {
+    let Ok((camera, projection)) = camera.get_single() else {
+        return;
+    };
+
+    let center = camera.translation.xy();
+    let scale = projection.scale;
+    let half = (projection.area.half_size() - settings.margin * scale).max(Vec2::ZERO);
+
+    for (entity, Indicator(target), mut transform, mut visibility, children) in &mut indicators {
+        let Ok((target, indicator)) = targets.get(*target) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let offset = target.translation.xy() - center;
+        let outside = offset.x.abs() > half.x || offset.y.abs() > half.y;
+        let edge = offset
+            * (half / offset.abs().max(Vec2::splat(f32::EPSILON)))
+                .min_element()
+                .min(1.0);
+        let distance = offset.distance(edge);
+        if !outside || distance > indicator.range {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        *visibility = Visibility::Inherited;
+        transform.translation = (center + edge).extend(50.0);
+        transform.scale = Vec3::splat(scale);
+
+        let direction = offset.normalize_or_zero();
+        for child in children {
+            if let Ok(mut arrow) = arrows.get_mut(*child) {
+                arrow.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x) + PI * 1.5);
+            }
+            if let Ok((mut label, mut text)) = labels.get_mut(*child) {
+                label.translation = (-direction * settings.size * 1.5).extend(0.0);
+                #[allow(clippy::cast_possible_truncation)]
+                let distance = (distance as i32).to_string();
+                text.sections[0].value = match (indicator.icon, indicator.distance) {
+                    (Some(icon), true) => format!("{icon} {distance}"),
+                    (Some(icon), false) => icon.to_string(),
+                    (None, true) => distance,
+                    (None, false) => String::new(),
+                };
+            }
+        }
+    }
+}
@@ -1,38 +1,117 @@
-use bevy::prelude::*;
+use bevy::{input::InputSystem, prelude::*};
 
 pub struct InputPlugin;
 
-#[derive(Debug, Event)]
+/// Player-specific events carry the index of the player slot they control.
+#[derive(Copy, Clone, Debug, PartialEq, Event)]
 pub enum InputEvent {
-    Brake,
-    Turn(f32),
-    Boost,
+    Brake(usize),
+    Turn(usize, f32),
+    Boost(usize),
     Pause,
+    ToggleShake,
+    CycleMinimap,
+    CycleDifficulty,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct KeyBindings {
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub brake: KeyCode,
+    pub boost: KeyCode,
+}
+
+/// Input is read, then synced with any peers, then applied to the players.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputSet {
+    Read,
+    Sync,
+    Apply,
+}
+
+#[derive(Resource)]
+pub struct Bindings(pub Vec<KeyBindings>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self(vec![
+            KeyBindings {
+                left: KeyCode::A,
+                right: KeyCode::D,
+                brake: KeyCode::S,
+                boost: KeyCode::ShiftLeft,
+            },
+            KeyBindings {
+                left: KeyCode::Left,
+                right: KeyCode::Right,
+                brake: KeyCode::Down,
+                boost: KeyCode::ShiftRight,
+            },
+            KeyBindings {
+                left: KeyCode::Numpad4,
+                right: KeyCode::Numpad6,
+                brake: KeyCode::Numpad5,
+                boost: KeyCode::Numpad0,
+            },
+            KeyBindings {
+                left: KeyCode::Delete,
+                right: KeyCode::PageDown,
+                brake: KeyCode::End,
+                boost: KeyCode::Home,
+            },
+        ])
+    }
 }
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<InputEvent>();
-        app.add_systems(Update, keyboard);
+        app.init_resource::<Bindings>();
+        app.configure_sets(
+            PreUpdate,
+            (InputSet::Read, InputSet::Sync, InputSet::Apply)
+                .chain()
+                .after(InputSystem),
+        );
+        app.add_systems(PreUpdate, keyboard.in_set(InputSet::Read));
     }
 }
 
-fn keyboard(keys: Res<Input<KeyCode>>, mut event_writer: EventWriter<InputEvent>) {
-    if keys.pressed(KeyCode::S) {
-        event_writer.send(InputEvent::Brake);
-    }
+fn keyboard(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<Bindings>,
+    mut event_writer: EventWriter<InputEvent>,
+) {
+    for (slot, binding) in bindings.0.iter().enumerate() {
+        if keys.pressed(binding.brake) {
+            event_writer.send(InputEvent::Brake(slot));
+        }
 
-    if keys.pressed(KeyCode::D) {
-        event_writer.send(InputEvent::Turn(-1.0));
-    } else if keys.pressed(KeyCode::A) {
-        event_writer.send(InputEvent::Turn(1.0));
-    }
+        if keys.pressed(binding.right) {
+            event_writer.send(InputEvent::Turn(slot, -1.0));
+        } else if keys.pressed(binding.left) {
+            event_writer.send(InputEvent::Turn(slot, 1.0));
+        }
 
-    if keys.just_pressed(KeyCode::ShiftLeft) {
-        event_writer.send(InputEvent::Boost);
+        if keys.just_pressed(binding.boost) {
+            event_writer.send(InputEvent::Boost(slot));
+        }
     }
 
     if keys.just_pressed(KeyCode::Escape) {
         event_writer.send(InputEvent::Pause);
     }
+
+    if keys.just_pressed(KeyCode::K) {
+        event_writer.send(InputEvent::ToggleShake);
+    }
+
+    if keys.just_pressed(KeyCode::M) {
+        event_writer.send(InputEvent::CycleMinimap);
+    }
+
+    if keys.just_pressed(KeyCode::L) {
+        event_writer.send(InputEvent::CycleDifficulty);
+    }
 }
@@ -2,17 +2,31 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::needless_pass_by_value)]
 
+mod arena;
 mod assets;
+mod audio;
 mod boid;
 mod camera;
 mod collectible;
+mod daily;
+mod difficulty;
 mod health;
+mod hitbox;
+mod indicator;
 mod input;
+mod minimap;
+mod mode;
+mod net;
+mod particle;
 mod player;
 mod points;
+mod popup;
+mod powerup;
 mod rng;
+mod scoring;
 mod shockwave;
 mod track;
+mod trail;
 mod ui;
 mod velocity;
 
@@ -25,20 +39,42 @@ impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<GameEvent>();
         app.add_state::<GameState>();
+        app.init_resource::<Wave>();
+        app.init_resource::<WaveScript>();
+        app.add_plugins(arena::Plugin);
         app.add_plugins(assets::Plugin);
+        app.add_plugins(audio::Plugin);
         app.add_plugins(input::InputPlugin);
         app.add_plugins(track::TrackPlugin);
         app.add_plugins(health::Plugin);
+        app.add_plugins(hitbox::Plugin);
         app.add_plugins(player::PlayerPlugin);
         app.add_plugins(points::PointsPlugin);
+        app.add_plugins(scoring::Plugin);
         app.add_plugins(rng::RngPlugin);
         app.add_plugins(camera::CameraPlugin);
         app.add_plugins(boid::BoidPlugin);
+        app.add_plugins(difficulty::Plugin);
+        app.add_plugins(mode::Plugin);
+        app.add_plugins(daily::Plugin);
         app.add_plugins(collectible::Plugin);
+        app.add_plugins(powerup::Plugin);
         app.add_plugins(shockwave::Plugin);
+        app.add_plugins(particle::Plugin);
+        app.add_plugins(trail::Plugin);
         app.add_plugins(ui::Plugin);
+        app.add_plugins(popup::Plugin);
+        app.add_plugins(minimap::Plugin);
+        app.add_plugins(indicator::Plugin);
         app.add_plugins(velocity::Plugin);
         app.add_systems(Update, waves.run_if(on_event::<GameEvent>()));
+        app.add_systems(OnExit(GameState::GameOver), reset);
+        app.add_systems(
+            Update,
+            restart
+                .run_if(resource_exists::<Restart>())
+                .run_if(in_state(GameState::GameOver)),
+        );
         app.add_systems(
             Update,
             (
@@ -55,12 +91,39 @@ pub enum GameEvent {
     NextWave { position: Vec2, velocity: Vec2 },
 }
 
+#[derive(Resource, Default)]
+pub struct Wave(pub u32);
+
+#[derive(Copy, Clone, Debug)]
+pub struct WaveSpawn {
+    pub bois: u32,
+    pub calm_bois: u32,
+}
+
+/// Waves to spawn in order, repeating from the start once exhausted.
+#[derive(Resource)]
+pub struct WaveScript(pub Vec<WaveSpawn>);
+
+impl Default for WaveScript {
+    fn default() -> Self {
+        Self(vec![WaveSpawn {
+            bois: 40,
+            calm_bois: 10,
+        }])
+    }
+}
+
+/// Passes straight through game over into a new run, without recording the abandoned one.
+#[derive(Resource)]
+pub struct Restart;
+
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 enum GameState {
     #[default]
     Playing,
     Paused,
     GameOver,
+    Connecting,
 }
 
 fn main() {
@@ -81,6 +144,9 @@ fn main() {
             }),
     );
     app.add_plugins(CorePlugin);
+    if let Some(config) = net::Config::from_args() {
+        app.add_plugins(net::Plugin(config));
+    }
 
     #[cfg(feature = "inspector")]
     #[allow(clippy::items_after_statements)]
@@ -105,19 +171,26 @@ fn main() {
     app.run();
 }
 
-fn waves(mut events: EventReader<GameEvent>, mut boid_events: EventWriter<boid::SpawnEvent>) {
+fn waves(
+    mut wave: ResMut<Wave>,
+    script: Res<WaveScript>,
+    mut events: EventReader<GameEvent>,
+    mut boid_events: EventWriter<boid::SpawnEvent>,
+) {
     for event in events.read() {
         match event {
             GameEvent::NextWave { position, velocity } => {
+                wave.0 += 1;
+                let spawn = script.0[(wave.0 as usize - 1) % script.0.len()];
                 boid_events.send(boid::SpawnEvent {
                     kind: boid::BoidKind::Boi,
-                    count: 40,
+                    count: spawn.bois,
                     position: *position,
                     velocity: *velocity,
                 });
                 boid_events.send(boid::SpawnEvent {
                     kind: boid::BoidKind::CalmBoi,
-                    count: 10,
+                    count: spawn.calm_bois,
                     position: *position,
                     velocity: *velocity,
                 });
@@ -126,6 +199,15 @@ fn waves(mut events: EventReader<GameEvent>, mut boid_events: EventWriter<boid::
     }
 }
 
+fn reset(mut wave: ResMut<Wave>) {
+    wave.0 = 0;
+}
+
+fn restart(mut commands: Commands, mut state: ResMut<NextState<GameState>>) {
+    commands.remove_resource::<Restart>();
+    state.set(GameState::Playing);
+}
+
 fn pause(mut input: EventReader<input::InputEvent>, mut state: ResMut<NextState<GameState>>) {
     for input in input.read() {
         if let input::InputEvent::Pause = input {
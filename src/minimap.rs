@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use bevy_spatial::kdtree::KDTree2;
+
+use crate::{
+    arena::Arena,
+    boid::{AngryBoi, Boid},
+    collectible::Collectible,
+    input::InputEvent,
+    player::Player,
+    track::Tracked,
+    velocity::Velocity,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MinimapSettings {
+            size: Vec2::new(200.0, 120.0),
+            corner: Corner::BottomRight,
+            margin: 8.0,
+            cells: UVec2::new(20, 12),
+            saturation: 12.0,
+        });
+        app.add_systems(Startup, setup);
+        app.add_systems(Update, layout.run_if(resource_changed::<MinimapSettings>()));
+        app.add_systems(Update, (density, markers));
+        app.add_systems(Update, cycle.run_if(on_event::<InputEvent>()));
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    fn next(self) -> Self {
+        match self {
+            Corner::TopLeft => Corner::TopRight,
+            Corner::TopRight => Corner::BottomRight,
+            Corner::BottomRight => Corner::BottomLeft,
+            Corner::BottomLeft => Corner::TopLeft,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct MinimapSettings {
+    pub size: Vec2,
+    pub corner: Corner,
+    pub margin: f32,
+    pub cells: UVec2,
+    /// Boids in one cell that show at full intensity.
+    pub saturation: f32,
+}
+
+#[derive(Component)]
+struct Minimap;
+
+#[derive(Component)]
+struct Cell(UVec2);
+
+/// A pooled dot; unused ones are hidden rather than despawned.
+#[derive(Component)]
+struct Marker;
+
+const MARKER_SIZE: f32 = 4.0;
+
+fn setup(mut commands: Commands, settings: Res<MinimapSettings>) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Minimap"));
+    entity.insert(Minimap);
+    entity.insert(NodeBundle {
+        background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+        border_color: BorderColor(Color::rgba(1.0, 1.0, 1.0, 0.3)),
+        style: Style {
+            position_type: PositionType::Absolute,
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        ..default()
+    });
+    entity.with_children(|parent| {
+        for x in 0..settings.cells.x {
+            for y in 0..settings.cells.y {
+                parent.spawn((
+                    Cell(UVec2::new(x, y)),
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ));
+            }
+        }
+    });
+}
+
+fn cycle(mut settings: ResMut<MinimapSettings>, mut input: EventReader<InputEvent>) {
+    for input in input.read() {
+        if let InputEvent::CycleMinimap = input {
+            settings.corner = settings.corner.next();
+        }
+    }
+}
+
+fn layout(
+    settings: Res<MinimapSettings>,
+    mut minimap: Query<&mut Style, (With<Minimap>, Without<Cell>)>,
+    mut cells: Query<(&mut Style, &Cell)>,
+) {
+    for mut style in &mut minimap {
+        let margin = Val::Px(settings.margin);
+        let (left, right, top, bottom) = match settings.corner {
+            Corner::TopLeft => (margin, Val::Auto, margin, Val::Auto),
+            Corner::TopRight => (Val::Auto, margin, margin, Val::Auto),
+            Corner::BottomLeft => (margin, Val::Auto, Val::Auto, margin),
+            Corner::BottomRight => (Val::Auto, margin, Val::Auto, margin),
+        };
+        style.left = left;
+        style.right = right;
+        style.top = top;
+        style.bottom = bottom;
+        style.width = Val::Px(settings.size.x);
+        style.height = Val::Px(settings.size.y);
+    }
+
+    let cell = settings.size / settings.cells.as_vec2();
+    for (mut style, Cell(index)) in &mut cells {
+        let offset = index.as_vec2() * cell;
+        style.left = Val::Px(offset.x);
+        style.bottom = Val::Px(offset.y);
+        style.width = Val::Px(cell.x);
+        style.height = Val::Px(cell.y);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn density(
+    settings: Res<MinimapSettings>,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    flock: Query<(), (With<Boid>, Without<AngryBoi>)>,
+    mut cells: Query<(&mut BackgroundColor, &Cell)>,
+) {
+    let bounds = arena.bounds();
+    let cell = bounds.size() / settings.cells.as_vec2();
+    let radius = cell.length() * 0.5;
+    for (mut color, Cell(index)) in &mut cells {
+        let min = bounds.min + index.as_vec2() * cell;
+        let bin = Rect::from_corners(min, min + cell);
+        // The query circle covers the cell's corners; only what lies inside the cell counts.
+        #[allow(clippy::cast_precision_loss)]
+        let count = arena
+            .within_distance(&quadtree, bin.center(), radius)
+            .into_iter()
+            .filter(|(pos, _)| bin.contains(*pos))
+            .filter_map(|(_pos, entity)| entity)
+            .filter(|entity| flock.contains(*entity))
+            .count() as f32;
+        let intensity = (count / settings.saturation).min(1.0);
+        color.0 = Color::rgba(0.3, 0.6, 1.0, intensity * 0.6);
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn markers(
+    mut commands: Commands,
+    settings: Res<MinimapSettings>,
+    arena: Res<Arena>,
+    minimap: Query<Entity, With<Minimap>>,
+    player: Query<(&Transform, &Velocity), With<Player>>,
+    collectibles: Query<&Transform, With<Collectible>>,
+    angry: Query<&Transform, With<AngryBoi>>,
+    mut pool: Query<(&mut Style, &mut BackgroundColor, &mut Visibility), With<Marker>>,
+) {
+    let Ok(minimap) = minimap.get_single() else {
+        return;
+    };
+
+    let heading = player.iter().map(|(transform, vel)| {
+        let nose = transform.translation.xy() + vel.normalize_or_zero() * 30.0;
+        (nose, Color::WHITE)
+    });
+    let dots = collectibles
+        .iter()
+        .map(|transform| (transform.translation.xy(), Color::GREEN))
+        .chain(
+            angry
+                .iter()
+                .map(|transform| (transform.translation.xy(), Color::RED)),
+        )
+        .chain(
+            player
+                .iter()
+                .map(|(transform, _)| (transform.translation.xy(), Color::YELLOW)),
+        )
+        .chain(heading)
+        .collect::<Vec<_>>();
+
+    let bounds = arena.bounds();
+    let mut dots = dots.into_iter();
+    for (mut style, mut color, mut visibility) in &mut pool {
+        let Some((position, dot)) = dots.next() else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let uv = ((position - bounds.min) / bounds.size()).clamp(Vec2::ZERO, Vec2::ONE);
+        let pixel = uv * settings.size - MARKER_SIZE * 0.5;
+        style.left = Val::Px(pixel.x);
+        style.bottom = Val::Px(pixel.y);
+        color.0 = dot;
+        *visibility = Visibility::Inherited;
+    }
+
+    commands.entity(minimap).with_children(|parent| {
+        for _ in dots {
+            parent.spawn((
+                Marker,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(MARKER_SIZE),
+                        height: Val::Px(MARKER_SIZE),
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+            ));
+        }
+    });
+}
@@ -0,0 +1,355 @@
+use std::{cmp::Reverse, fmt::Write, fs};
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{
+    arena::{Arena, Placement},
+    boid::{BoidKind, SpawnEvent},
+    daily::{DailyChallenge, Date},
+    difficulty::Difficulty,
+    player::{Party, PlayStyle, Player},
+    points::{PointEvent, PointSource, Points, Scores},
+    rng::RngSource,
+    GameState, Restart,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>();
+        app.insert_resource(HighScores::load());
+        app.init_resource::<Survival>();
+        app.add_systems(
+            Update,
+            (clock, survival).run_if(in_state(GameState::Playing)),
+        );
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            record.run_if(not(resource_exists::<Restart>())),
+        );
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+#[derive(Resource, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    TimeAttack,
+    Zen,
+    Survival,
+    Daily,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Rules {
+    pub time_limit: Option<f32>,
+    pub mortal: bool,
+    pub angry_waves: bool,
+    pub angry_interval: Option<f32>,
+    pub survival_points: u32,
+}
+
+const SURVIVAL_TICK: f32 = 5.0;
+
+impl GameMode {
+    pub const ALL: [GameMode; 5] = [
+        GameMode::Endless,
+        GameMode::TimeAttack,
+        GameMode::Zen,
+        GameMode::Survival,
+        GameMode::Daily,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameMode::Endless => "Endless",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::Zen => "Zen",
+            GameMode::Survival => "Survival",
+            GameMode::Daily => "Daily",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            GameMode::Endless => "endless",
+            GameMode::TimeAttack => "time_attack",
+            GameMode::Zen => "zen",
+            GameMode::Survival => "survival",
+            GameMode::Daily => "daily",
+        }
+    }
+
+    pub fn rules(self) -> Rules {
+        let endless = Rules {
+            time_limit: None,
+            mortal: true,
+            angry_waves: true,
+            angry_interval: None,
+            survival_points: 0,
+        };
+        match self {
+            GameMode::Endless | GameMode::Daily => endless,
+            GameMode::TimeAttack => Rules {
+                time_limit: Some(120.0),
+                ..endless
+            },
+            GameMode::Zen => Rules {
+                mortal: false,
+                angry_waves: false,
+                ..endless
+            },
+            GameMode::Survival => Rules {
+                angry_interval: Some(4.0),
+                survival_points: 10,
+                ..endless
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighScore {
+    pub mode: GameMode,
+    pub points: u32,
+    pub date: Option<Date>,
+}
+
+#[derive(Resource, Default)]
+pub struct HighScores {
+    scores: Vec<HighScore>,
+    /// Last day a daily challenge was played for score, whether or not it made the list.
+    daily: Option<Date>,
+}
+
+const HIGH_SCORES: &str = "highscores.txt";
+const KEPT_SCORES: usize = 10;
+const DAILY_KEY: &str = "played";
+
+impl HighScores {
+    pub fn iter(&self) -> impl Iterator<Item = &HighScore> {
+        self.scores.iter()
+    }
+
+    pub fn played_daily(&self, date: Date) -> bool {
+        self.daily == Some(date)
+    }
+
+    pub fn mark_daily(&mut self, date: Date) {
+        self.daily = Some(date);
+        self.save();
+    }
+
+    fn load() -> Self {
+        fs::read_to_string(HIGH_SCORES)
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Err(error) = fs::write(HIGH_SCORES, self.serialize()) {
+            warn!("Could not save high scores: {error}");
+        }
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut scores = Self::default();
+        for line in contents.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(key) = fields.next() else {
+                continue;
+            };
+            if key == DAILY_KEY {
+                scores.daily = fields.next().and_then(Date::parse);
+                continue;
+            }
+            let Some(mode) = GameMode::ALL.into_iter().find(|mode| mode.key() == key) else {
+                continue;
+            };
+            let Some(points) = fields.next().and_then(|points| points.parse().ok()) else {
+                continue;
+            };
+            scores.scores.push(HighScore {
+                mode,
+                points,
+                date: fields.next().and_then(Date::parse),
+            });
+        }
+        scores.scores.sort_by_key(|score| Reverse(score.points));
+        scores
+    }
+
+    fn serialize(&self) -> String {
+        let mut contents = String::new();
+        if let Some(date) = self.daily {
+            let _ = writeln!(contents, "{DAILY_KEY} {date}");
+        }
+        for score in &self.scores {
+            let _ = write!(contents, "{} {}", score.mode.key(), score.points);
+            if let Some(date) = score.date {
+                let _ = write!(contents, " {date}");
+            }
+            let _ = writeln!(contents);
+        }
+        contents
+    }
+
+    fn insert(&mut self, score: HighScore) {
+        let index = self
+            .scores
+            .partition_point(|other| other.points >= score.points);
+        self.scores.insert(index, score);
+        self.scores.truncate(KEPT_SCORES);
+    }
+}
+
+#[derive(Resource, Default)]
+struct Survival {
+    spawn: f32,
+    points: f32,
+}
+
+fn clock(
+    mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    mut state: ResMut<NextState<GameState>>,
+) {
+    let Some(limit) = mode.rules().time_limit else {
+        return;
+    };
+    if difficulty.elapsed >= limit {
+        state.set(GameState::GameOver);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn survival(
+    mode: Res<GameMode>,
+    arena: Res<Arena>,
+    mut timers: ResMut<Survival>,
+    players: Query<(&Player, &Transform)>,
+    mut rng: ResMut<RngSource>,
+    mut boid_events: EventWriter<SpawnEvent>,
+    mut point_events: EventWriter<PointEvent>,
+    time: Res<Time>,
+) {
+    let rules = mode.rules();
+    let players = players
+        .iter()
+        .map(|(player, transform)| (player.slot(), transform.translation.xy()))
+        .collect::<Vec<_>>();
+    if players.is_empty() {
+        return;
+    }
+
+    if let Some(interval) = rules.angry_interval {
+        timers.spawn += time.delta_seconds();
+        if timers.spawn >= interval {
+            timers.spawn -= interval;
+            if let Some(&(_, player)) = players.choose(&mut **rng) {
+                let position = arena.sample(Placement::Edge, player, &mut **rng);
+                boid_events.send(SpawnEvent {
+                    kind: BoidKind::AngryBoi,
+                    count: 1,
+                    position,
+                    velocity: arena.delta(position, player).normalize_or_zero(),
+                });
+            }
+        }
+    }
+
+    if rules.survival_points > 0 {
+        timers.points += time.delta_seconds();
+        if timers.points >= SURVIVAL_TICK {
+            timers.points -= SURVIVAL_TICK;
+            for &(slot, position) in &players {
+                point_events.send(PointEvent::Add {
+                    amount: rules.survival_points,
+                    source: PointSource::Survival,
+                    position,
+                    slot,
+                });
+            }
+        }
+    }
+}
+
+pub fn record(
+    mode: Res<GameMode>,
+    challenge: Res<DailyChallenge>,
+    party: Res<Party>,
+    points: Res<Points>,
+    player_scores: Res<Scores>,
+    mut scores: ResMut<HighScores>,
+) {
+    // Versus runs are ranked by the winning score, co-op runs by the team total.
+    let points = match party.style {
+        PlayStyle::Versus => player_scores.best().1,
+        PlayStyle::Coop => points.0,
+    };
+    let date = match *mode {
+        GameMode::Daily if !challenge.scored => return,
+        GameMode::Daily => challenge.date,
+        _ => None,
+    };
+    if points > 0 {
+        scores.insert(HighScore {
+            mode: *mode,
+            points,
+            date,
+        });
+    }
+    scores.save();
+}
+
+fn reset(mut timers: ResMut<Survival>) {
+    *timers = Survival::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn high_scores_round_trip() {
+        let date = Date::parse("2026-10-18");
+        let mut scores = HighScores {
+            scores: Vec::new(),
+            daily: date,
+        };
+        scores.insert(HighScore {
+            mode: GameMode::Zen,
+            points: 40,
+            date: None,
+        });
+        scores.insert(HighScore {
+            mode: GameMode::Daily,
+            points: 120,
+            date,
+        });
+
+        let parsed = HighScores::parse(&scores.serialize());
+        assert_eq!(parsed.scores, scores.scores);
+        assert!(parsed.played_daily(date.unwrap()));
+    }
+
+    #[test]
+    fn high_scores_skip_bad_lines() {
+        let parsed = HighScores::parse(
+            "endless 10\n\nunknown 50\nsurvival lots\nplayed never\ntime_attack 30 someday\n",
+        );
+        let expected = [(GameMode::TimeAttack, 30), (GameMode::Endless, 10)];
+        assert_eq!(
+            parsed
+                .iter()
+                .map(|score| (score.mode, score.points))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert!(parsed.iter().all(|score| score.date.is_none()));
+        assert_eq!(parsed.daily, None);
+    }
+}
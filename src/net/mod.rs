@@ -0,0 +1,653 @@
+mod packet;
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    env,
+    hash::{Hash, Hasher},
+    io,
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use bevy::{
+    ecs::{
+        event::ManualEventReader,
+        schedule::{ExecutorKind, ScheduleLabel},
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    window::PrimaryWindow,
+};
+
+use bevy_spatial::TimestepLength;
+
+use crate::{
+    health::Health,
+    input::{InputEvent, InputSet},
+    player::{Lives, Party, PlayStyle, Player},
+    points::Points,
+    rng::RngSource,
+    track::Tracked,
+    GameState, Restart, Wave,
+};
+
+use packet::{Frame, Packet};
+
+const DEFAULT_DELAY: u32 = 3;
+const TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+const PLAYERS: usize = 2;
+const CHECKSUM_INTERVAL: u32 = 30;
+const CHECKSUM_WINDOW: u32 = CHECKSUM_INTERVAL * 8;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+const HELLO_INTERVAL: Duration = Duration::from_millis(100);
+const TIMEOUT: Duration = Duration::from_secs(10);
+const RESEND_INTERVAL: Duration = Duration::from_millis(20);
+const STALL_NOTICE: Duration = Duration::from_millis(250);
+/// Bye is never acknowledged, so it goes out a few times in case some are lost.
+const BYE_REPEATS: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub local: SocketAddr,
+    pub peer: SocketAddr,
+    pub delay: u32,
+}
+
+impl Config {
+    pub fn from_args() -> Option<Self> {
+        let args = env::args().collect::<Vec<_>>();
+        let index = args.iter().position(|arg| arg == "--connect")?;
+        let addresses = (args.get(index + 1), args.get(index + 2));
+        let (Some(Ok(local)), Some(Ok(peer))) = (
+            addresses.0.map(|address| address.parse()),
+            addresses.1.map(|address| address.parse()),
+        ) else {
+            warn!("Usage: --connect <local address> <peer address> [--delay <ticks>]");
+            return None;
+        };
+        let delay = args
+            .iter()
+            .position(|arg| arg == "--delay")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|delay| delay.parse().ok())
+            .unwrap_or(DEFAULT_DELAY);
+        Some(Self { local, peer, delay })
+    }
+}
+
+pub struct Plugin(pub Config);
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            exchange
+                .in_set(InputSet::Sync)
+                .run_if(resource_exists::<Session>()),
+        );
+        app.add_systems(
+            Update,
+            handshake
+                .run_if(resource_exists::<Handshake>())
+                .run_if(in_state(GameState::Connecting)),
+        );
+        app.add_systems(OnEnter(GameState::Connecting), freeze);
+        app.add_systems(OnExit(GameState::Connecting), thaw);
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            end.run_if(resource_exists::<Session>()),
+        );
+        app.add_systems(
+            OnExit(GameState::GameOver),
+            (reset, begin.run_if(resource_exists::<Starting>())),
+        );
+
+        let handshake = match Handshake::bind(&self.0) {
+            Ok(handshake) => handshake,
+            Err(error) => {
+                warn!(
+                    "Could not connect to {}, playing locally: {error}",
+                    self.0.peer
+                );
+                return;
+            }
+        };
+        info!("Waiting for {}", self.0.peer);
+        app.insert_resource(handshake);
+        app.insert_resource(State::new(GameState::Connecting));
+
+        // The multithreaded executor may order unrelated systems differently on each peer.
+        for (_, schedule) in app.world.resource_mut::<Schedules>().iter_mut() {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        }
+        // The spatial index rebuilds on its own timer, which must fire on the same ticks everywhere.
+        app.world
+            .resource_mut::<TimestepLength<Tracked>>()
+            .set_duration(TICK);
+
+        let mut lockstep = Schedule::new(Lockstep);
+        lockstep.set_executor_kind(ExecutorKind::SingleThreaded);
+        app.add_schedule(lockstep);
+        app.add_systems(Lockstep, step);
+        app.main_schedule_label = Lockstep.intern();
+    }
+}
+
+#[derive(Resource)]
+pub struct Disconnected(pub String);
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Lockstep;
+
+#[derive(Resource)]
+struct Handshake {
+    socket: UdpSocket,
+    slot: usize,
+    seed: u64,
+    delay: u32,
+    started: Instant,
+    last_sent: Option<Instant>,
+}
+
+impl Handshake {
+    fn bind(config: &Config) -> io::Result<Self> {
+        let socket = UdpSocket::bind(config.local)?;
+        socket.connect(config.peer)?;
+        socket.set_nonblocking(true)?;
+        // The peer with the lower address plays first and picks the seed.
+        let slot = usize::from(config.local > config.peer);
+        Ok(Self {
+            socket,
+            slot,
+            seed: if slot == 0 { rand::random() } else { 0 },
+            delay: config.delay.max(1),
+            started: Instant::now(),
+            last_sent: None,
+        })
+    }
+
+    fn hello(&self) {
+        // Sending fails until the peer is listening.
+        let hello = Packet::Hello {
+            seed: self.seed,
+            delay: self.delay,
+        };
+        let _ = self.socket.send(&hello.encode());
+    }
+
+    fn poll(&mut self) -> bool {
+        if self
+            .last_sent
+            .map_or(true, |sent| sent.elapsed() >= HELLO_INTERVAL)
+        {
+            self.hello();
+            self.last_sent = Some(Instant::now());
+        }
+        let mut buffer = [0; 2048];
+        while let Ok(length) = self.socket.recv(&mut buffer) {
+            if let Some(Packet::Hello { seed, delay }) = Packet::decode(&buffer[..length]) {
+                // Both peers play on slot 0's seed and input delay.
+                if self.slot == 1 {
+                    self.seed = seed;
+                    self.delay = delay.max(1);
+                }
+                self.hello();
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[derive(Resource)]
+struct Starting(Session);
+
+#[derive(Resource)]
+pub struct Session {
+    socket: UdpSocket,
+    slot: usize,
+    seed: u64,
+    delay: u32,
+    tick: u32,
+    local: BTreeMap<u32, Frame>,
+    remote: BTreeMap<u32, Frame>,
+    /// Every peer tick below this has arrived.
+    received: u32,
+    /// Every local tick below this has reached the peer.
+    acked: u32,
+    checksums: BTreeMap<u32, u64>,
+    remote_checksums: BTreeMap<u32, u64>,
+    peer_left: bool,
+    last_sent: Instant,
+    stalled: Option<Instant>,
+    title: Option<String>,
+    reader: ManualEventReader<InputEvent>,
+}
+
+impl Session {
+    fn new(handshake: Handshake) -> Self {
+        let delay = handshake.delay;
+        Self {
+            socket: handshake.socket,
+            slot: handshake.slot,
+            seed: handshake.seed,
+            delay,
+            tick: 0,
+            local: BTreeMap::new(),
+            // Nobody has input for the first ticks, so they run empty.
+            remote: (0..delay).map(|tick| (tick, Frame::new())).collect(),
+            received: delay,
+            acked: delay,
+            checksums: BTreeMap::new(),
+            remote_checksums: BTreeMap::new(),
+            peer_left: false,
+            last_sent: Instant::now(),
+            stalled: None,
+            title: None,
+            reader: ManualEventReader::default(),
+        }
+    }
+
+    fn send(&mut self, packet: &Packet) {
+        if let Err(error) = self.socket.send(&packet.encode()) {
+            debug!("Could not send to peer: {error}");
+        }
+        self.last_sent = Instant::now();
+    }
+
+    fn send_inputs(&mut self) {
+        let packet = Packet::Inputs {
+            ack: self.received,
+            frames: self
+                .local
+                .range(self.acked..)
+                .map(|(tick, frame)| (*tick, frame.clone()))
+                .collect(),
+            checksums: self
+                .checksums
+                .iter()
+                .map(|(tick, checksum)| (*tick, *checksum))
+                .collect(),
+        };
+        self.send(&packet);
+    }
+
+    fn bye(&mut self) {
+        for _ in 0..BYE_REPEATS {
+            self.send(&Packet::Bye);
+        }
+    }
+
+    fn poll(&mut self) {
+        let mut buffer = [0; 2048];
+        while let Ok(length) = self.socket.recv(&mut buffer) {
+            match Packet::decode(&buffer[..length]) {
+                // The peer missed our side of the handshake.
+                Some(Packet::Hello { .. }) => self.send(&Packet::Hello {
+                    seed: self.seed,
+                    delay: self.delay,
+                }),
+                Some(Packet::Inputs {
+                    ack,
+                    frames,
+                    checksums,
+                }) => {
+                    self.acked = self.acked.max(ack);
+                    for (tick, frame) in frames {
+                        if tick >= self.received {
+                            self.remote.entry(tick).or_insert(frame);
+                        }
+                    }
+                    while self.remote.contains_key(&self.received) {
+                        self.received += 1;
+                    }
+                    self.remote_checksums.extend(checksums);
+                }
+                // Keep running on buffered inputs; the peer may simply be a few ticks ahead.
+                Some(Packet::Bye) => self.peer_left = true,
+                None => debug!("Dropped a malformed packet"),
+            }
+        }
+    }
+
+    fn desync(&mut self) -> Option<u32> {
+        let tick = self.tick;
+        self.remote_checksums
+            .retain(|tick_taken, _| tick_taken + CHECKSUM_WINDOW >= tick);
+        let mut matched = Vec::new();
+        for (tick_taken, remote) in &self.remote_checksums {
+            match self.checksums.get(tick_taken) {
+                Some(local) if local != remote => return Some(*tick_taken),
+                Some(_) => matched.push(*tick_taken),
+                None => {}
+            }
+        }
+        for tick_taken in matched {
+            self.checksums.remove(&tick_taken);
+            self.remote_checksums.remove(&tick_taken);
+        }
+        self.checksums
+            .retain(|tick_taken, _| tick_taken + CHECKSUM_WINDOW >= tick);
+        None
+    }
+
+    fn ready(&mut self) -> Result<bool, String> {
+        self.poll();
+        if let Some(tick) = self.desync() {
+            return Err(format!("Out of sync at tick {tick}"));
+        }
+        if self.remote.contains_key(&self.tick) {
+            self.stalled = None;
+            return Ok(true);
+        }
+        if self.peer_left {
+            return Err("The other player left".to_string());
+        }
+        if self.stalled.get_or_insert_with(Instant::now).elapsed() > TIMEOUT {
+            return Err("The other player stopped responding".to_string());
+        }
+        if self.last_sent.elapsed() >= RESEND_INTERVAL {
+            self.send_inputs();
+        }
+        Ok(false)
+    }
+
+    fn advance(&mut self) -> Vec<InputEvent> {
+        let remote = self.remote.remove(&self.tick).unwrap_or_default();
+        let local = self.local.get(&self.tick).cloned().unwrap_or_default();
+        let mut events = Vec::new();
+        for slot in 0..PLAYERS {
+            let frame = if slot == self.slot { &local } else { &remote };
+            events.extend(frame.iter().map(|event| with_slot(*event, slot)));
+        }
+        self.tick += 1;
+        let done = self.acked.min(self.tick);
+        self.local.retain(|tick, _| *tick >= done);
+        events
+    }
+}
+
+fn controls_local_player(event: &InputEvent) -> bool {
+    match event {
+        InputEvent::Brake(slot) | InputEvent::Turn(slot, _) | InputEvent::Boost(slot) => *slot == 0,
+        _ => true,
+    }
+}
+
+fn with_slot(event: InputEvent, slot: usize) -> InputEvent {
+    match event {
+        InputEvent::Brake(_) => InputEvent::Brake(slot),
+        InputEvent::Turn(_, direction) => InputEvent::Turn(slot, direction),
+        InputEvent::Boost(_) => InputEvent::Boost(slot),
+        other => other,
+    }
+}
+
+/// Entity order can differ between peers, so entries are summed.
+fn checksum(world: &mut World) -> u64 {
+    let mut tracked = world.query_filtered::<&Transform, With<Tracked>>();
+    let mut sum = 0u64;
+    for transform in tracked.iter(world) {
+        let mut hasher = DefaultHasher::new();
+        transform
+            .translation
+            .to_array()
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+        transform
+            .rotation
+            .to_array()
+            .map(f32::to_bits)
+            .hash(&mut hasher);
+        sum = sum.wrapping_add(hasher.finish());
+    }
+    let mut players = world.query::<(&Player, &Health, &Lives)>();
+    for (player, health, lives) in players.iter(world) {
+        let mut hasher = DefaultHasher::new();
+        (player.slot(), health.0, lives.0).hash(&mut hasher);
+        sum = sum.wrapping_add(hasher.finish());
+    }
+    let mut hasher = DefaultHasher::new();
+    world.resource::<Points>().0.hash(&mut hasher);
+    world.resource::<Wave>().0.hash(&mut hasher);
+    // Equal positions in the stream mean both peers drew the same number of rolls.
+    world
+        .resource::<RngSource>()
+        .get_word_pos()
+        .hash(&mut hasher);
+    sum.wrapping_add(hasher.finish())
+}
+
+/// Holds whole frames back until the peer's inputs for them are in.
+fn step(world: &mut World) {
+    let Some(mut session) = world.remove_resource::<Session>() else {
+        world.run_schedule(Main);
+        return;
+    };
+    match session.ready() {
+        Ok(true) => {
+            if let Some(title) = session.title.take() {
+                set_title(world, title);
+            }
+            world.insert_resource(session);
+            world.run_schedule(Main);
+        }
+        Ok(false) => {
+            let waited = session
+                .stalled
+                .map_or(Duration::ZERO, |since| since.elapsed());
+            if waited > STALL_NOTICE && session.title.is_none() {
+                let title = set_title(world, "Waiting for the other player...".to_string());
+                session.title = Some(title);
+            }
+            world.insert_resource(session);
+            world.run_schedule(Last);
+        }
+        Err(reason) => {
+            warn!("Disconnected: {reason}");
+            session.bye();
+            if let Some(title) = session.title.take() {
+                set_title(world, title);
+            }
+            world.insert_resource(Disconnected(reason));
+            world.insert_resource(TimeUpdateStrategy::Automatic);
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::GameOver);
+            world.run_schedule(Main);
+        }
+    }
+}
+
+fn set_title(world: &mut World, title: String) -> String {
+    let mut windows = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+    windows
+        .get_single_mut(world)
+        .map(|mut window| std::mem::replace(&mut window.title, title))
+        .unwrap_or_default()
+}
+
+fn handshake(world: &mut World) {
+    let mut handshake = world.resource_mut::<Handshake>();
+    let connected = handshake.poll();
+    if connected {
+        let Some(handshake) = world.remove_resource::<Handshake>() else {
+            return;
+        };
+        let session = Session::new(handshake);
+        info!("Connected as {}", crate::player::name(session.slot));
+        world.insert_resource(Party {
+            players: PLAYERS,
+            style: PlayStyle::Versus,
+        });
+        world.insert_resource(RngSource::seeded(session.seed));
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(TICK));
+        world.insert_resource(Starting(session));
+        world.insert_resource(Restart);
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::GameOver);
+    } else if world.resource::<Handshake>().started.elapsed() > HANDSHAKE_TIMEOUT {
+        warn!("Nobody answered, playing locally");
+        world.remove_resource::<Handshake>();
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+    }
+}
+
+fn freeze(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn thaw(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn begin(world: &mut World) {
+    let Some(Starting(session)) = world.remove_resource::<Starting>() else {
+        return;
+    };
+    // Keys read while the run was starting would only reach this peer.
+    world.resource_mut::<Events<InputEvent>>().clear();
+    world.insert_resource(session);
+}
+
+fn exchange(world: &mut World) {
+    let Some(mut session) = world.remove_resource::<Session>() else {
+        return;
+    };
+
+    let mut events = world.resource_mut::<Events<InputEvent>>();
+    let read = session.reader.read(&events).copied().collect::<Vec<_>>();
+    events.clear();
+    let (synced, local): (Vec<_>, Vec<_>) = read.into_iter().partition(packet::synced);
+    let frame = synced
+        .into_iter()
+        .filter(controls_local_player)
+        .collect::<Frame>();
+    session.local.insert(session.tick + session.delay, frame);
+
+    if session.tick % CHECKSUM_INTERVAL == 0 {
+        let checksum = checksum(world);
+        session.checksums.insert(session.tick, checksum);
+    }
+    session.send_inputs();
+
+    let mut events = world.resource_mut::<Events<InputEvent>>();
+    events.extend(local);
+    events.extend(session.advance());
+    // Skip what was just sent so it is not sent to the peer again next frame.
+    session.reader.read(&events).for_each(drop);
+    world.insert_resource(session);
+}
+
+fn end(mut commands: Commands, mut session: ResMut<Session>) {
+    session.bye();
+    commands.remove_resource::<Session>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+}
+
+fn reset(mut commands: Commands) {
+    commands.remove_resource::<Disconnected>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use rand::Rng;
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Slot(usize);
+
+    fn peer(socket: UdpSocket, slot: usize) -> App {
+        socket.set_nonblocking(true).unwrap();
+        let session = Session::new(Handshake {
+            socket,
+            slot,
+            seed: 7,
+            delay: 2,
+            started: Instant::now(),
+            last_sent: None,
+        });
+        let mut app = App::new();
+        app.add_event::<InputEvent>();
+        app.insert_resource(Points(0));
+        app.insert_resource(Wave(0));
+        app.insert_resource(RngSource::seeded(session.seed));
+        app.insert_resource(session);
+        for slot in 0..PLAYERS {
+            app.world
+                .spawn((Slot(slot), Tracked, TransformBundle::default()));
+        }
+        app
+    }
+
+    /// Stands in for the game: every synced input moves state and some draw from the shared RNG.
+    fn simulate(world: &mut World) {
+        let events = world
+            .resource::<Events<InputEvent>>()
+            .iter_current_update_events()
+            .copied()
+            .collect::<Vec<_>>();
+        let mut slots = world.query::<(&Slot, &mut Transform)>();
+        for event in events {
+            let (slot, step) = match event {
+                InputEvent::Turn(slot, direction) => (slot, Vec2::new(direction, 0.0)),
+                InputEvent::Boost(slot) => {
+                    let roll = world.resource_mut::<RngSource>().gen::<f32>();
+                    world.resource_mut::<Points>().0 += 1;
+                    (slot, Vec2::new(0.0, roll))
+                }
+                _ => continue,
+            };
+            for (owner, mut transform) in slots.iter_mut(world) {
+                if owner.0 == slot {
+                    transform.translation += step.extend(0.0);
+                }
+            }
+        }
+    }
+
+    fn tick(app: &mut App, inputs: &[InputEvent]) {
+        app.world.send_event_batch(inputs.iter().copied());
+        while !app.world.resource_mut::<Session>().ready().unwrap() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        exchange(&mut app.world);
+        simulate(&mut app.world);
+    }
+
+    #[test]
+    fn peers_stay_in_sync() {
+        let sockets = [
+            UdpSocket::bind("127.0.0.1:0").unwrap(),
+            UdpSocket::bind("127.0.0.1:0").unwrap(),
+        ];
+        let addresses = sockets
+            .each_ref()
+            .map(|socket| socket.local_addr().unwrap());
+        sockets[0].connect(addresses[1]).unwrap();
+        sockets[1].connect(addresses[0]).unwrap();
+        let [first, second] = sockets;
+        let mut peers = [peer(first, 0), peer(second, 1)];
+
+        for tick_index in 0..CHECKSUM_INTERVAL * 4 {
+            let direction = if tick_index % 3 == 0 { 1.0 } else { -1.0 };
+            tick(&mut peers[0], &[InputEvent::Turn(0, direction)]);
+            let boost = if tick_index % 5 == 0 {
+                vec![InputEvent::Boost(0)]
+            } else {
+                Vec::new()
+            };
+            tick(&mut peers[1], &boost);
+        }
+
+        let [first, second] = &mut peers;
+        assert_eq!(checksum(&mut first.world), checksum(&mut second.world));
+        assert!(first.world.resource::<Points>().0 > 0);
+    }
+}
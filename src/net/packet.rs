@@ -0,0 +1,247 @@
+use crate::input::InputEvent;
+
+pub type Frame = Vec<InputEvent>;
+
+#[derive(Debug, PartialEq)]
+pub enum Packet {
+    Hello {
+        seed: u64,
+        delay: u32,
+    },
+    Inputs {
+        ack: u32,
+        frames: Vec<(u32, Frame)>,
+        checksums: Vec<(u32, u64)>,
+    },
+    Bye,
+}
+
+const HELLO: u8 = 0;
+const INPUTS: u8 = 1;
+const BYE: u8 = 2;
+
+const BRAKE: u8 = 0;
+const TURN: u8 = 1;
+const BOOST: u8 = 2;
+const PAUSE: u8 = 3;
+const CYCLE_DIFFICULTY: u8 = 4;
+
+impl Packet {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Packet::Hello { seed, delay } => {
+                bytes.push(HELLO);
+                bytes.extend(seed.to_le_bytes());
+                bytes.extend(delay.to_le_bytes());
+            }
+            Packet::Inputs {
+                ack,
+                frames,
+                checksums,
+            } => {
+                bytes.push(INPUTS);
+                bytes.extend(ack.to_le_bytes());
+                bytes.push(u8::try_from(frames.len()).unwrap_or(u8::MAX));
+                for (tick, frame) in frames.iter().take(u8::MAX.into()) {
+                    let events = frame
+                        .iter()
+                        .filter(|event| synced(event))
+                        .take(u8::MAX.into())
+                        .collect::<Vec<_>>();
+                    bytes.extend(tick.to_le_bytes());
+                    bytes.push(u8::try_from(events.len()).unwrap_or(u8::MAX));
+                    for event in events {
+                        encode_event(&mut bytes, event);
+                    }
+                }
+                bytes.push(u8::try_from(checksums.len()).unwrap_or(u8::MAX));
+                for (tick, checksum) in checksums.iter().take(u8::MAX.into()) {
+                    bytes.extend(tick.to_le_bytes());
+                    bytes.extend(checksum.to_le_bytes());
+                }
+            }
+            Packet::Bye => bytes.push(BYE),
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let packet = match reader.u8()? {
+            HELLO => Packet::Hello {
+                seed: reader.u64()?,
+                delay: reader.u32()?,
+            },
+            INPUTS => {
+                let ack = reader.u32()?;
+                let mut frames = Vec::new();
+                for _ in 0..reader.u8()? {
+                    let tick = reader.u32()?;
+                    let mut frame = Frame::new();
+                    for _ in 0..reader.u8()? {
+                        frame.push(decode_event(&mut reader)?);
+                    }
+                    frames.push((tick, frame));
+                }
+                let mut checksums = Vec::new();
+                for _ in 0..reader.u8()? {
+                    checksums.push((reader.u32()?, reader.u64()?));
+                }
+                Packet::Inputs {
+                    ack,
+                    frames,
+                    checksums,
+                }
+            }
+            BYE => Packet::Bye,
+            _ => return None,
+        };
+        reader.0.is_empty().then_some(packet)
+    }
+}
+
+pub fn synced(event: &InputEvent) -> bool {
+    !matches!(event, InputEvent::ToggleShake | InputEvent::CycleMinimap)
+}
+
+/// Player events go out with slot 0; the receiver swaps in the sender's slot.
+fn encode_event(bytes: &mut Vec<u8>, event: &InputEvent) {
+    match event {
+        InputEvent::Brake(_) => bytes.push(BRAKE),
+        InputEvent::Turn(_, direction) => {
+            bytes.push(TURN);
+            bytes.extend(direction.to_le_bytes());
+        }
+        InputEvent::Boost(_) => bytes.push(BOOST),
+        InputEvent::Pause => bytes.push(PAUSE),
+        InputEvent::CycleDifficulty => bytes.push(CYCLE_DIFFICULTY),
+        InputEvent::ToggleShake | InputEvent::CycleMinimap => {}
+    }
+}
+
+fn decode_event(reader: &mut Reader) -> Option<InputEvent> {
+    Some(match reader.u8()? {
+        BRAKE => InputEvent::Brake(0),
+        TURN => InputEvent::Turn(0, reader.f32()?),
+        BOOST => InputEvent::Boost(0),
+        PAUSE => InputEvent::Pause,
+        CYCLE_DIFFICULTY => InputEvent::CycleDifficulty,
+        _ => return None,
+    })
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, tail) = self.0.split_first_chunk::<N>()?;
+        self.0 = tail;
+        Some(*head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.take().map(f32::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, Packet};
+    use crate::input::InputEvent;
+
+    fn inputs() -> Packet {
+        Packet::Inputs {
+            ack: 7,
+            frames: vec![
+                (7, Frame::new()),
+                (
+                    8,
+                    vec![
+                        InputEvent::Brake(0),
+                        InputEvent::Turn(0, -1.0),
+                        InputEvent::Boost(0),
+                        InputEvent::Pause,
+                        InputEvent::CycleDifficulty,
+                    ],
+                ),
+            ],
+            checksums: vec![(0, u64::MAX), (30, 42)],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for packet in [
+            Packet::Hello {
+                seed: 1234,
+                delay: 3,
+            },
+            inputs(),
+            Packet::Bye,
+        ] {
+            assert_eq!(Packet::decode(&packet.encode()), Some(packet));
+        }
+    }
+
+    #[test]
+    fn local_events_are_not_sent() {
+        let packet = Packet::Inputs {
+            ack: 0,
+            frames: vec![(3, vec![InputEvent::ToggleShake, InputEvent::Boost(0)])],
+            checksums: Vec::new(),
+        };
+        let expected = Packet::Inputs {
+            ack: 0,
+            frames: vec![(3, vec![InputEvent::Boost(0)])],
+            checksums: Vec::new(),
+        };
+        assert_eq!(Packet::decode(&packet.encode()), Some(expected));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(Packet::decode(&[]), None);
+        assert_eq!(Packet::decode(&[9]), None);
+
+        let mut trailing = Packet::Bye.encode();
+        trailing.push(0);
+        assert_eq!(Packet::decode(&trailing), None);
+
+        // Inputs with one frame holding one event of an unknown kind.
+        let mut unknown_event = vec![1];
+        unknown_event.extend(0u32.to_le_bytes());
+        unknown_event.push(1);
+        unknown_event.extend(0u32.to_le_bytes());
+        unknown_event.extend([1, 9, 0]);
+        assert_eq!(Packet::decode(&unknown_event), None);
+    }
+
+    #[test]
+    fn truncated() {
+        for packet in [
+            Packet::Hello {
+                seed: 1234,
+                delay: 3,
+            },
+            inputs(),
+        ] {
+            let bytes = packet.encode();
+            for length in 0..bytes.len() {
+                assert_eq!(Packet::decode(&bytes[..length]), None);
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::{f32::consts::PI, ops::Range, sync::Arc};
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology, view::NoFrustumCulling},
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    transform::TransformSystem,
+};
+use interpolation::{Ease, EaseFunction, Lerp};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{assets::Images, GameState};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.insert_resource(ParticleSettings { capacity: 4096 });
+        app.init_resource::<Particles>();
+        app.init_resource::<ParticleRng>();
+        app.add_systems(Startup, setup);
+        app.add_systems(
+            PostUpdate,
+            (
+                (emit, burst.run_if(on_event::<Event>()), simulate)
+                    .run_if(not(in_state(GameState::Paused))),
+                render,
+            )
+                .chain()
+                .after(TransformSystem::TransformPropagate),
+        );
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Burst { position: Vec2, emitter: Emitter },
+}
+
+/// Kept apart from `RngSource` so cosmetic spawns never shift gameplay rolls.
+#[derive(Resource, Deref, DerefMut)]
+struct ParticleRng(ChaCha8Rng);
+
+impl Default for ParticleRng {
+    fn default() -> Self {
+        Self(ChaCha8Rng::from_entropy())
+    }
+}
+
+#[derive(Resource)]
+pub struct ParticleSettings {
+    pub capacity: usize,
+}
+
+#[derive(Debug)]
+pub struct ParticleStyle {
+    ramp: Vec<Vec4>,
+    scale: Range<f32>,
+    ease: EaseFunction,
+    /// Fraction of velocity lost per second.
+    drag: f32,
+}
+
+impl ParticleStyle {
+    pub fn new(ramp: &[Color]) -> Self {
+        assert!(!ramp.is_empty());
+        Self {
+            ramp: ramp
+                .iter()
+                .map(|color| Vec4::from(color.as_linear_rgba_f32()))
+                .collect(),
+            scale: 1.0..1.0,
+            ease: EaseFunction::QuadraticIn,
+            drag: 0.0,
+        }
+    }
+
+    pub fn with_scale(mut self, scale: Range<f32>, ease: EaseFunction) -> Self {
+        self.scale = scale;
+        self.ease = ease;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    fn color(&self, t: f32) -> Vec4 {
+        #[allow(clippy::cast_precision_loss)]
+        let position = t.clamp(0.0, 1.0) * (self.ramp.len() - 1) as f32;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let index = (position.floor() as usize).min(self.ramp.len() - 1);
+        let next = (index + 1).min(self.ramp.len() - 1);
+        self.ramp[index].lerp(self.ramp[next], position.fract())
+    }
+
+    fn scale(&self, t: f32) -> f32 {
+        self.scale
+            .start
+            .lerp(&self.scale.end, &t.clamp(0.0, 1.0).calc(self.ease))
+    }
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Emitter {
+    pub style: Arc<ParticleStyle>,
+    pub rate: f32,
+    pub burst: u32,
+    pub active: bool,
+    pub lifetime: Range<f32>,
+    pub speed: Range<f32>,
+    pub direction: Vec2,
+    /// Half-angle of the emission cone; `PI` emits in every direction.
+    pub spread: f32,
+    pub radius: f32,
+    pub size: Range<f32>,
+    pub spin: f32,
+}
+
+impl Emitter {
+    pub fn new(style: ParticleStyle) -> Self {
+        Self {
+            style: Arc::new(style),
+            rate: 0.0,
+            burst: 0,
+            active: true,
+            lifetime: 1.0..1.0,
+            speed: 0.0..0.0,
+            direction: Vec2::Y,
+            spread: PI,
+            radius: 0.0,
+            size: 8.0..8.0,
+            spin: 0.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Emission(f32);
+
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    rotation: f32,
+    spin: f32,
+    size: f32,
+    age: f32,
+    lifetime: f32,
+    style: Arc<ParticleStyle>,
+}
+
+#[derive(Resource, Default)]
+struct Particles(Vec<Particle>);
+
+#[derive(Component)]
+struct ParticleMesh;
+
+fn sample(rng: &mut impl Rng, range: &Range<f32>) -> f32 {
+    range.start.lerp(&range.end, &rng.gen::<f32>())
+}
+
+impl Particles {
+    fn spawn(
+        &mut self,
+        capacity: usize,
+        emitter: &Emitter,
+        origin: Vec2,
+        rotation: Quat,
+        count: u32,
+        rng: &mut impl Rng,
+    ) {
+        let direction = (rotation * emitter.direction.extend(0.0)).xy();
+        let heading = direction.y.atan2(direction.x);
+        for _ in 0..count {
+            if self.0.len() >= capacity {
+                return;
+            }
+            let angle = heading + sample(rng, &(-emitter.spread..emitter.spread));
+            let direction = Vec2::from_angle(angle);
+            self.0.push(Particle {
+                position: origin + direction * emitter.radius,
+                velocity: direction * sample(rng, &emitter.speed),
+                rotation: rng.gen::<f32>() * PI * 2.0,
+                spin: sample(rng, &(-emitter.spin..emitter.spin)),
+                size: sample(rng, &emitter.size),
+                age: 0.0,
+                lifetime: sample(rng, &emitter.lifetime).max(f32::EPSILON),
+                style: emitter.style.clone(),
+            });
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    images: Res<Images>,
+    settings: Res<ParticleSettings>,
+    mut particles: ResMut<Particles>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    particles.0.reserve(settings.capacity);
+
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Particles"));
+    entity.insert(ParticleMesh);
+    entity.insert(NoFrustumCulling);
+    entity.insert(MaterialMesh2dBundle {
+        mesh: meshes
+            .add(Mesh::new(PrimitiveTopology::TriangleList))
+            .into(),
+        material: materials.add(ColorMaterial::from(images.smoke.clone())),
+        transform: Transform::from_xyz(0.0, 0.0, 1.0),
+        visibility: Visibility::Hidden,
+        ..default()
+    });
+}
+
+fn emit(
+    mut commands: Commands,
+    settings: Res<ParticleSettings>,
+    mut particles: ResMut<Particles>,
+    mut emitters: Query<(Entity, &GlobalTransform, &Emitter, Option<&mut Emission>)>,
+    mut rng: ResMut<ParticleRng>,
+    time: Res<Time>,
+) {
+    for (entity, transform, emitter, emission) in &mut emitters {
+        let (_scale, rotation, translation) = transform.to_scale_rotation_translation();
+        let Some(mut emission) = emission else {
+            commands.entity(entity).insert(Emission(0.0));
+            particles.spawn(
+                settings.capacity,
+                emitter,
+                translation.xy(),
+                rotation,
+                emitter.burst,
+                &mut **rng,
+            );
+            continue;
+        };
+        if !emitter.active {
+            continue;
+        }
+
+        emission.0 += emitter.rate * time.delta_seconds();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = emission.0.floor() as u32;
+        #[allow(clippy::cast_precision_loss)]
+        {
+            emission.0 -= count as f32;
+        }
+        if count > 0 {
+            particles.spawn(
+                settings.capacity,
+                emitter,
+                translation.xy(),
+                rotation,
+                count,
+                &mut **rng,
+            );
+        }
+    }
+}
+
+fn burst(
+    settings: Res<ParticleSettings>,
+    mut particles: ResMut<Particles>,
+    mut events: EventReader<Event>,
+    mut rng: ResMut<ParticleRng>,
+) {
+    for event in events.read() {
+        let Event::Burst { position, emitter } = event;
+        particles.spawn(
+            settings.capacity,
+            emitter,
+            *position,
+            Quat::IDENTITY,
+            emitter.burst,
+            &mut **rng,
+        );
+    }
+}
+
+fn simulate(mut particles: ResMut<Particles>, time: Res<Time>) {
+    let dt = time.delta_seconds();
+    for particle in &mut particles.0 {
+        particle.age += dt;
+        particle.velocity *= (1.0 - particle.style.drag * dt).max(0.0);
+        particle.position += particle.velocity * dt;
+        particle.rotation += particle.spin * dt;
+    }
+    particles
+        .0
+        .retain(|particle| particle.age < particle.lifetime);
+}
+
+fn render(
+    particles: Res<Particles>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh: Query<(&Mesh2dHandle, &mut Visibility), With<ParticleMesh>>,
+) {
+    let Ok((handle, mut visibility)) = mesh.get_single_mut() else {
+        return;
+    };
+    if particles.0.is_empty() {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    let Some(mesh) = meshes.get_mut(&handle.0) else {
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    let count = particles.0.len();
+    let mut positions = Vec::with_capacity(count * 4);
+    let mut uvs = Vec::with_capacity(count * 4);
+    let mut colors = Vec::with_capacity(count * 4);
+    let mut indices = Vec::with_capacity(count * 6);
+    for particle in &particles.0 {
+        let t = particle.age / particle.lifetime;
+        let half = particle.size * particle.style.scale(t) * 0.5;
+        let right = Vec2::from_angle(particle.rotation) * half;
+        let up = right.perp();
+        let color = particle.style.color(t).to_array();
+
+        #[allow(clippy::cast_possible_truncation)]
+        let base = positions.len() as u32;
+        for (corner, uv) in [
+            (-right - up, [0.0, 1.0]),
+            (right - up, [1.0, 1.0]),
+            (right + up, [1.0, 0.0]),
+            (-right + up, [0.0, 0.0]),
+        ] {
+            positions.push((particle.position + corner).extend(0.0).to_array());
+            uvs.push(uv);
+            colors.push(color);
+        }
+        indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.set_indices(Some(Indices::U32(indices)));
+}
+
+fn reset(mut particles: ResMut<Particles>) {
+    particles.0.clear();
+}
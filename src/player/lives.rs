@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    arena::{Arena, Placement},
+    boid::BoidSettings,
+    health::{self, Health, Invulnerable},
+    particle,
+    rng::RngSource,
+    shockwave,
+    trail::Trail,
+    velocity::Velocity,
+};
+
+use super::{spawn_position, Player, PlayerSettings};
+
+pub(super) struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Respawn>();
+        app.add_systems(Update, hit.run_if(on_event::<health::Event>()));
+        app.add_systems(Update, respawn.run_if(on_event::<Respawn>()));
+        app.add_systems(Update, (flash, blink));
+    }
+}
+
+#[derive(Component)]
+pub struct Lives(pub u32);
+
+#[derive(Event)]
+pub(super) struct Respawn(pub Entity);
+
+#[derive(Component)]
+struct Flash {
+    remaining: f32,
+    color: Color,
+}
+
+#[allow(clippy::type_complexity)]
+fn hit(
+    mut commands: Commands,
+    settings: Res<PlayerSettings>,
+    mut player: Query<(&Transform, &mut Velocity, &Sprite, Option<&Flash>), With<Player>>,
+    attackers: Query<&Transform, Without<Player>>,
+    mut events: EventReader<health::Event>,
+) {
+    for event in events.read() {
+        let health::Event::Damaged {
+            target,
+            attacker: Some(attacker),
+            ..
+        } = event
+        else {
+            continue;
+        };
+
+        let Ok((transform, mut vel, sprite, flash)) = player.get_mut(*target) else {
+            continue;
+        };
+
+        if let Ok(attacker) = attackers.get(*attacker) {
+            let away = transform.translation.xy() - attacker.translation.xy();
+            vel.0 += away.normalize_or_zero() * settings.knockback;
+        }
+
+        commands.entity(*target).insert(Flash {
+            remaining: 0.2,
+            color: flash.map_or(sprite.color, |flash| flash.color),
+        });
+    }
+}
+
+fn flash(
+    mut commands: Commands,
+    mut flashing: Query<(Entity, &mut Flash, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut sprite) in &mut flashing {
+        flash.remaining -= time.delta_seconds();
+        if flash.remaining > 0.0 {
+            sprite.color = Color::RED;
+        } else {
+            sprite.color = flash.color;
+            commands.entity(entity).remove::<Flash>();
+        }
+    }
+}
+
+fn blink(
+    mut blinking: Query<(&Invulnerable, &mut Visibility), With<Player>>,
+    mut restored: Query<&mut Visibility, (With<Player>, Without<Invulnerable>)>,
+    mut removed: RemovedComponents<Invulnerable>,
+) {
+    for (invulnerable, mut visibility) in &mut blinking {
+        #[allow(clippy::cast_possible_truncation)]
+        let on = (invulnerable.0 * 10.0) as i32 % 2 == 0;
+        *visibility = if on {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for entity in removed.read() {
+        if let Ok(mut visibility) = restored.get_mut(entity) {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn respawn(
+    mut commands: Commands,
+    settings: Res<PlayerSettings>,
+    boid_settings: Res<BoidSettings>,
+    arena: Res<Arena>,
+    mut player: Query<(
+        &mut Player,
+        &mut Transform,
+        &mut Velocity,
+        &mut Health,
+        Option<&mut Trail>,
+    )>,
+    mut events: EventReader<Respawn>,
+    mut shockwave_events: EventWriter<shockwave::Event>,
+    mut particle_events: EventWriter<particle::Event>,
+    mut rng: ResMut<RngSource>,
+) {
+    for Respawn(entity) in events.read() {
+        let Ok((mut player, mut transform, mut vel, mut health, trail)) = player.get_mut(*entity)
+        else {
+            continue;
+        };
+
+        particle_events.send(super::death_burst(transform.translation.xy(), 80));
+
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: transform.translation.xy(),
+            radius: 300.,
+            duration: Duration::from_secs_f32(1.0),
+            color: Color::RED,
+            effect: shockwave::Effect::Stun(2.0),
+            falloff: shockwave::Falloff::Constant,
+            affects_player: false,
+            affects_collectibles: false,
+            source: None,
+        });
+
+        let placement = Placement::Away(arena.bounds().size().min_element() * 0.5);
+        let pos = spawn_position(&arena, placement, transform.translation.xy(), &mut rng);
+        transform.translation = pos;
+        if let Some(mut trail) = trail {
+            trail.clear();
+        }
+        vel.0 = (arena.center() - pos.xy()).normalize_or_zero();
+        player.target_linvel = boid_settings.top_speed();
+        health.0 = settings.health;
+        commands
+            .entity(*entity)
+            .insert(Invulnerable(settings.respawn_invulnerability));
+
+        // Angry bois caught at the landing spot calm down instead of waiting there.
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: pos.xy(),
+            radius: 100.,
+            duration: Duration::from_secs_f32(0.5),
+            color: Color::WHITE,
+            effect: shockwave::Effect::Convert,
+            falloff: shockwave::Falloff::Linear,
+            affects_player: false,
+            affects_collectibles: false,
+            source: None,
+        });
+    }
+}
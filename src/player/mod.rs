@@ -1,51 +1,97 @@
-mod offscreen_marker;
+mod lives;
+mod party;
 
 use std::{f32::consts::PI, time::Duration};
 
 use bevy::{audio::PlaybackMode, prelude::*};
-use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
-use interpolation::{Ease, Lerp};
-use rand::{seq::IteratorRandom, Rng};
+use interpolation::{Ease, EaseFunction, Lerp};
 
 use crate::{
+    arena::{Arena, Placement},
     assets::{Images, Sounds},
+    audio::{self, Bus, Channel, Mixer, SoundGroup},
     boid::{Alignment, BoidSettings},
-    collectible::{self, Collectible},
-    health::{self, Health},
-    input::InputEvent,
-    points::PointEvent,
+    collectible::{self, Collectible, CollectibleKind},
+    health::{self, DamageKind, Health, MaxHealth},
+    hitbox::{self, BaseScale, Hitbox, Hurtbox, Layer, Shape},
+    indicator::OffscreenIndicator,
+    input::{InputEvent, InputSet},
+    mode::GameMode,
+    particle::{self, Emitter, ParticleStyle},
+    powerup,
     rng::RngSource,
-    shockwave,
+    scoring::{self, Combo, Multiplier},
+    shockwave::{self, Stunned},
     track::Tracked,
+    trail::Trail,
     velocity::Velocity,
     GameEvent,
 };
 
+pub use lives::Lives;
+pub use party::{name, tint, Party, PlayStyle, MAX_PLAYERS};
+
+#[derive(Resource, Default)]
+pub struct CauseOfDeath(pub Option<(DamageKind, Option<String>)>);
+
+/// Slot of the last player standing in a versus run.
+#[derive(Resource, Default)]
+pub struct Winner(pub Option<usize>);
+
 const MIN_SCALE: Vec2 = Vec2::new(0.2, 0.2);
 const MAX_SCALE: Vec2 = Vec2::new(1.0, 1.0);
 
 #[derive(Component, Default)]
 pub struct Player {
+    slot: usize,
     target_linvel: f32,
     angvel: f32,
     turn_speed: f32,
 }
 
+#[derive(Resource)]
+pub struct PlayerSettings {
+    pub health: u32,
+    pub lives: u32,
+    pub respawn: bool,
+    pub respawn_invulnerability: f32,
+    pub knockback: f32,
+}
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.insert_resource(PlayerSettings {
+            health: 3,
+            lives: 2,
+            respawn: true,
+            respawn_invulnerability: 2.0,
+            knockback: 300.0,
+        });
+        app.init_resource::<CauseOfDeath>();
+        app.init_resource::<Winner>();
+        app.init_resource::<Party>();
         app.add_systems(Startup, startup);
-        app.add_systems(OnExit(crate::GameState::GameOver), startup);
+        app.add_systems(
+            OnExit(crate::GameState::GameOver),
+            (startup, reset).after(crate::daily::apply),
+        );
         app.add_systems(Update, movement.run_if(in_state(crate::GameState::Playing)));
         app.add_systems(OnEnter(crate::GameState::Paused), pause);
         app.add_systems(OnExit(crate::GameState::Paused), unpause);
-        app.add_systems(Update, collect);
+        app.add_systems(Update, collect.run_if(on_event::<hitbox::Event>()));
         app.add_systems(
             Update,
             boost_cooldown.run_if(in_state(crate::GameState::Playing)),
         );
-        app.add_systems(PreUpdate, input.run_if(on_event::<InputEvent>()));
+        app.add_systems(
+            PreUpdate,
+            input
+                .in_set(InputSet::Apply)
+                .run_if(on_event::<InputEvent>()),
+        );
+        app.add_systems(Update, exhaust);
         app.add_systems(Update, fast_removes_alignment);
         app.add_systems(Update, slow_adds_alignment);
         app.add_systems(Update, die.run_if(on_event::<health::Event>()));
@@ -54,62 +100,110 @@ impl Plugin for PlayerPlugin {
             engine_audio.run_if(in_state(crate::GameState::Playing)),
         );
         app.add_systems(OnEnter(crate::GameState::GameOver), gameover);
-        app.add_plugins(offscreen_marker::Plugin);
+        app.add_plugins(lives::Plugin);
 
         #[cfg(feature = "inspector")]
         app.register_type::<Boost>();
     }
 }
 
+fn spawn_position(arena: &Arena, placement: Placement, origin: Vec2, rng: &mut RngSource) -> Vec3 {
+    arena.sample(placement, origin, &mut **rng).extend(10.0)
+}
+
+impl Player {
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn startup(
     mut commands: Commands,
     settings: Res<BoidSettings>,
+    player_settings: Res<PlayerSettings>,
+    party: Res<Party>,
     images: Res<Images>,
     sounds: Res<Sounds>,
+    mixer: Res<Mixer>,
+    arena: Res<Arena>,
     mut rng: ResMut<RngSource>,
 ) {
-    let pos = Vec3::new(
-        rng.gen::<f32>() * 1000. - 500.,
-        rng.gen::<f32>() * 600. - 300.,
-        10.0,
-    );
-    let mut entity = commands.spawn_empty();
-    entity.insert(Name::new("player"));
-    entity.insert(SpriteBundle {
-        sprite: Sprite {
-            color: Color::GREEN,
+    for slot in 0..party.players.clamp(1, MAX_PLAYERS) {
+        let pos = spawn_position(&arena, Placement::Uniform, Vec2::ZERO, &mut rng);
+        let color = tint(slot);
+        let mut entity = commands.spawn_empty();
+        entity.insert(Name::new(name(slot)));
+        entity.insert(SpriteBundle {
+            sprite: Sprite { color, ..default() },
+            texture: images.player.clone(),
             ..default()
-        },
-        texture: images.player.clone(),
-        ..default()
-    });
-    entity.insert(Player {
-        target_linvel: settings.max_speed,
-        angvel: 0.0,
-        turn_speed: 1.5,
-    });
-    entity.insert(Tracked);
-    entity.insert(Health(1));
-    entity.insert(Velocity(-pos.xy().normalize_or_zero()));
-    entity.insert(Alignment::default());
-    entity.insert(Boost::new(4.));
-    entity.insert(Brake::new(2000.));
-    entity.insert(TransformBundle {
-        local: Transform::from_translation(pos),
-        ..default()
-    });
-    entity.insert(AudioBundle {
-        source: sounds.player_engine.clone(),
-        settings: PlaybackSettings {
-            mode: PlaybackMode::Loop,
+        });
+        entity.insert(Player {
+            slot,
+            target_linvel: settings.top_speed(),
+            angvel: 0.0,
+            turn_speed: 1.5,
+        });
+        entity.insert(Tracked);
+        entity.insert(OffscreenIndicator::new(color));
+        entity.insert(Hitbox {
+            shape: Shape::Circle(28.0),
+            layer: Layer::Player,
+        });
+        entity.insert(Hurtbox {
+            shape: Shape::Circle(14.0),
+            layer: Layer::Player,
+        });
+        // The sprite shrinks with speed, but the hitboxes keep their full size.
+        entity.insert(BaseScale(MAX_SCALE.extend(1.0)));
+        entity.insert(Health(player_settings.health));
+        entity.insert(MaxHealth(player_settings.health));
+        entity.insert(Lives(player_settings.lives));
+        entity.insert(Velocity((arena.center() - pos.xy()).normalize_or_zero()));
+        entity.insert(Alignment::default());
+        entity.insert(Boost::new(4.));
+        entity.insert(Combo::default());
+        entity.insert(Multiplier::default());
+        entity.insert(Trail::new(color.with_a(0.6), 10.0));
+        entity.insert(Emitter {
+            rate: 80.0,
+            active: false,
+            lifetime: 0.3..0.6,
+            speed: 40.0..90.0,
+            direction: Vec2::NEG_Y,
+            spread: 0.35,
+            radius: 10.0,
+            size: 6.0..12.0,
+            spin: 4.0,
+            ..Emitter::new(
+                ParticleStyle::new(&[
+                    Color::YELLOW,
+                    Color::ORANGE_RED,
+                    Color::rgba(0.3, 0.3, 0.3, 0.0),
+                ])
+                .with_scale(1.0..0.3, EaseFunction::QuadraticOut),
+            )
+        });
+        entity.insert(Brake::new(2000.));
+        entity.insert(TransformBundle {
+            local: Transform::from_translation(pos),
             ..default()
-        },
-    });
+        });
+        let channel = Channel::new(Bus::Engine);
+        entity.insert(AudioBundle {
+            source: sounds.player_engine.clone(),
+            settings: mixer
+                .settings(&channel, PlaybackMode::Loop)
+                .with_spatial(true),
+        });
+        entity.insert(channel);
+    }
 }
 
 #[derive(Component)]
 #[cfg_attr(feature = "inspector", derive(Reflect))]
-struct Boost {
+pub struct Boost {
     cooldown: f32,
     multiplier: f32,
 }
@@ -121,6 +215,14 @@ impl Boost {
             multiplier,
         }
     }
+
+    pub fn active(&self) -> bool {
+        self.cooldown > 0.0
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        self.multiplier
+    }
 }
 
 #[derive(Component)]
@@ -143,112 +245,160 @@ fn boost_cooldown(mut boost: Query<&mut Boost>, time: Res<Time>) {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 fn input(
-    mut commands: Commands,
-    mut player: Query<(&mut Player, &Transform, &mut Boost, &Brake)>,
+    mut players: Query<(Entity, &mut Player, &Transform, &mut Boost, &Brake)>,
     mut input: EventReader<InputEvent>,
     mut shockwave_events: EventWriter<shockwave::Event>,
-    mut rng: ResMut<RngSource>,
-    sounds: Res<Sounds>,
+    mut audio_events: EventWriter<audio::Event>,
     settings: Res<BoidSettings>,
+    party: Res<Party>,
     time: Res<Time>,
 ) {
-    let Ok((mut player, transform, mut boost, brake)) = player.get_single_mut() else {
-        return;
-    };
-
     for event in input.read() {
+        let slot = match event {
+            InputEvent::Boost(slot) | InputEvent::Brake(slot) | InputEvent::Turn(slot, _) => *slot,
+            InputEvent::Pause
+            | InputEvent::ToggleShake
+            | InputEvent::CycleMinimap
+            | InputEvent::CycleDifficulty => continue,
+        };
+        let Some((entity, mut player, transform, mut boost, brake)) = players
+            .iter_mut()
+            .find(|(_, player, ..)| player.slot == slot)
+        else {
+            continue;
+        };
+
         match event {
-            InputEvent::Boost => {
-                if boost.cooldown <= 0.0 {
-                    boost.cooldown = 1.0;
-                    player.target_linvel = settings.max_speed * boost.multiplier;
+            InputEvent::Boost(_) if boost.cooldown <= 0.0 => {
+                boost.cooldown = 1.0;
+                player.target_linvel = settings.top_speed() * boost.multiplier;
+                shockwave_events.send(shockwave::Event::Spawn {
+                    position: transform.translation.xy(),
+                    radius: 100.,
+                    duration: Duration::from_secs_f32(0.5),
+                    color: Color::YELLOW,
+                    effect: shockwave::Effect::Repel,
+                    falloff: shockwave::Falloff::Linear,
+                    affects_player: party.style == PlayStyle::Versus,
+                    affects_collectibles: false,
+                    source: Some(entity),
+                });
+                if party.style == PlayStyle::Versus {
+                    // Rivals caught right next to a boost get stung as well as shoved.
                     shockwave_events.send(shockwave::Event::Spawn {
                         position: transform.translation.xy(),
-                        radius: 100.,
-                        duration: Duration::from_secs_f32(0.5),
-                        color: Color::YELLOW,
-                        repel: true,
-                    });
-                    commands.spawn(AudioBundle {
-                        source: sounds.boost.iter().choose(&mut **rng).unwrap().clone(),
-                        settings: PlaybackSettings {
-                            mode: PlaybackMode::Remove,
-                            ..default()
-                        },
+                        radius: 60.,
+                        duration: Duration::from_secs_f32(0.3),
+                        color: Color::ORANGE_RED,
+                        effect: shockwave::Effect::Damage(1),
+                        falloff: shockwave::Falloff::Constant,
+                        affects_player: true,
+                        affects_collectibles: false,
+                        source: Some(entity),
                     });
                 }
+                audio_events.send(audio::Event::Play {
+                    group: SoundGroup::Boost,
+                    position: transform.translation.xy(),
+                });
             }
-            InputEvent::Brake => {
+            InputEvent::Brake(_) => {
                 player.target_linvel -= time.delta_seconds() * brake.power;
             }
-            InputEvent::Turn(dir) => {
+            InputEvent::Turn(_, dir) => {
                 player.angvel += dir * player.turn_speed * 2.;
                 player.angvel = player.angvel.clamp(-player.turn_speed, player.turn_speed);
             }
-            InputEvent::Pause => {}
+            _ => {}
         }
     }
 }
 
 fn movement(
     settings: Res<BoidSettings>,
-    mut player: Query<(&mut Player, &mut Velocity, &mut Transform, &Boost)>,
+    mut players: Query<(
+        &mut Player,
+        &mut Velocity,
+        &mut Transform,
+        &Boost,
+        Has<Stunned>,
+    )>,
     time: Res<Time>,
 ) {
-    let Ok((mut player, mut vel, mut transform, boost)) = player.get_single_mut() else {
-        return;
-    };
-
-    // Bounds
-    let radians = vel.0.y.atan2(vel.0.x);
-    let pos = transform.translation.xy();
-    let mut angle = radians + player.angvel * time.delta_seconds() * 5.0;
-    if !settings.bounds.contains(pos) {
-        let up = -transform.up().xy();
-        angle -= pos.angle_between(up) * time.delta_seconds() * 3.;
+    for (mut player, mut vel, mut transform, boost, stunned) in &mut players {
+        if !stunned {
+            // Steering
+            let radians = vel.0.y.atan2(vel.0.x);
+            let angle = radians + player.angvel * time.delta_seconds() * 5.0;
+
+            // Translation
+            vel.0 = Vec2::from_angle(angle) * vel.0.length();
+            let target_speed = vel.0.normalize_or_zero() * player.target_linvel;
+            vel.0 = vel.0.lerp(target_speed, time.delta_seconds() * 10.0);
+        }
+
+        // Rotation
+        transform.rotation = Quat::from_axis_angle(Vec3::Z, vel.0.y.atan2(vel.0.x) + PI * 1.5);
+
+        // Scale
+        let ratio = vel.0.length() / (settings.top_speed() * boost.multiplier - 0.5);
+        transform.scale = (MIN_SCALE)
+            .lerp(MAX_SCALE, ratio.quadratic_out())
+            .extend(0.0);
+
+        // Friction
+        player.target_linvel = player
+            .target_linvel
+            .lerp(&settings.top_speed(), &(time.delta_seconds() * 0.5));
+        player.angvel = 0.0;
+
+        // Clamp
+        player.target_linvel = player.target_linvel.clamp(
+            settings.top_speed() * 0.5,
+            settings.top_speed() * boost.multiplier,
+        );
+    }
+}
+
+fn exhaust(mut player: Query<(&Boost, &mut Emitter), With<Player>>) {
+    for (boost, mut emitter) in &mut player {
+        if emitter.active != boost.active() {
+            emitter.active = boost.active();
+        }
     }
+}
 
-    // Translation
-    vel.0 = Vec2::from_angle(angle) * vel.0.length();
-    let target_speed = vel.0.normalize_or_zero() * player.target_linvel;
-    vel.0 = vel.0.lerp(target_speed, time.delta_seconds() * 10.0);
-
-    // Rotation
-    transform.rotation = Quat::from_axis_angle(Vec3::Z, vel.0.y.atan2(vel.0.x) + PI * 1.5);
-
-    // Scale
-    let ratio = vel.0.length() / (settings.max_speed * boost.multiplier - 0.5);
-    transform.scale = (MIN_SCALE)
-        .lerp(MAX_SCALE, ratio.quadratic_out())
-        .extend(0.0);
-
-    // Friction
-    player.target_linvel = player
-        .target_linvel
-        .lerp(&settings.max_speed, &(time.delta_seconds() * 0.5));
-    player.angvel = 0.0;
-
-    // Clamp
-    player.target_linvel = player.target_linvel.clamp(
-        settings.max_speed * 0.5,
-        settings.max_speed * boost.multiplier,
-    );
+/// Debris thrown out where the player dies.
+fn death_burst(position: Vec2, count: u32) -> particle::Event {
+    particle::Event::Burst {
+        position,
+        emitter: Emitter {
+            burst: count,
+            lifetime: 0.5..1.4,
+            speed: 60.0..320.0,
+            size: 6.0..18.0,
+            spin: 8.0,
+            ..Emitter::new(
+                ParticleStyle::new(&[Color::WHITE, Color::RED, Color::rgba(0.2, 0.0, 0.0, 0.0)])
+                    .with_scale(1.0..0.2, EaseFunction::QuadraticIn)
+                    .with_drag(2.5),
+            )
+        },
+    }
 }
 
 #[allow(clippy::type_complexity)]
 fn fast_removes_alignment(
     mut commands: Commands,
     settings: Res<BoidSettings>,
-    player: Query<(Entity, &Velocity), (With<Player>, With<Alignment>)>,
+    players: Query<(Entity, &Velocity), (With<Player>, With<Alignment>)>,
 ) {
-    let Ok((entity, vel)) = player.get_single() else {
-        return;
-    };
-
-    if vel.length_squared() > (settings.max_speed * settings.max_speed) * 4.0 {
-        commands.entity(entity).remove::<Alignment>();
+    for (entity, vel) in &players {
+        if vel.length_squared() > (settings.top_speed() * settings.top_speed()) * 4.0 {
+            commands.entity(entity).remove::<Alignment>();
+        }
     }
 }
 
@@ -256,123 +406,220 @@ fn fast_removes_alignment(
 fn slow_adds_alignment(
     mut commands: Commands,
     settings: Res<BoidSettings>,
-    player: Query<(Entity, &Velocity), (With<Player>, Without<Alignment>)>,
+    players: Query<(Entity, &Velocity), (With<Player>, Without<Alignment>)>,
 ) {
-    let Ok((entity, vel)) = player.get_single() else {
-        return;
-    };
-
-    if vel.length_squared() < (settings.max_speed * settings.max_speed) * 4.0 {
-        commands.entity(entity).insert(Alignment::default());
+    for (entity, vel) in &players {
+        if vel.length_squared() < (settings.top_speed() * settings.top_speed()) * 4.0 {
+            commands.entity(entity).insert(Alignment::default());
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn collect(
-    quadtree: Res<KDTree2<Tracked>>,
     player: Query<(&Transform, &Velocity), With<Player>>,
-    collectibles: Query<(Entity, &Collectible), Without<collectible::Cooldown>>,
-    mut point_event: EventWriter<PointEvent>,
+    collectibles: Query<&Collectible, Without<collectible::Cooldown>>,
+    mut overlaps: EventReader<hitbox::Event>,
+    mut scoring_events: EventWriter<scoring::Event>,
     mut collectible_event: EventWriter<collectible::Event>,
     mut game_events: EventWriter<GameEvent>,
     mut shockwave_events: EventWriter<shockwave::Event>,
+    mut power_up_events: EventWriter<powerup::Event>,
+    mut heal_events: EventWriter<health::Heal>,
 ) {
-    for (transform, velocity) in player.iter() {
-        let pos = transform.translation.xy();
-        for (collectible_position, entity) in quadtree
-            .within_distance(pos, 32.0)
-            .into_iter()
-            .filter_map(|(pos, entity)| entity.map(|entity| (pos, entity)))
-        {
-            if let Ok((entity, collectible)) = collectibles.get(entity) {
-                point_event.send(PointEvent::Add(collectible.value));
-                collectible_event.send(collectible::Event::Collect(entity));
-
-                game_events.send(GameEvent::NextWave {
-                    position: transform.translation.xy(),
-                    velocity: velocity.0,
-                });
+    let mut collected = Vec::new();
+    for event in overlaps.read() {
+        let hitbox::Event::Overlap {
+            hitbox,
+            hurtbox: entity,
+            layer: Layer::Player,
+            target: Layer::Collectible,
+            position: collectible_position,
+        } = event
+        else {
+            continue;
+        };
+        let (Ok((transform, velocity)), Ok(collectible)) =
+            (player.get(*hitbox), collectibles.get(*entity))
+        else {
+            continue;
+        };
+        if collected.contains(entity) {
+            continue;
+        }
+        collected.push(*entity);
+
+        if let CollectibleKind::PowerUp(power_up) = collectible.kind {
+            power_up_events.send(powerup::Event::Activate {
+                target: *hitbox,
+                power_up,
+            });
+            collectible_event.send(collectible::Event::Collect(*entity));
+            continue;
+        }
 
-                shockwave_events.send(shockwave::Event::Spawn {
-                    position: collectible_position,
-                    radius: 100.,
-                    duration: Duration::from_secs_f32(1.),
-                    color: Color::GREEN,
-                    repel: false,
-                });
-            }
+        scoring_events.send(scoring::Event::Collect {
+            value: collectible.value,
+            position: *collectible_position,
+            player: *hitbox,
+        });
+        collectible_event.send(collectible::Event::Collect(*entity));
+        if collectible.heal > 0 {
+            heal_events.send(health::Heal {
+                target: *hitbox,
+                amount: collectible.heal,
+            });
         }
+
+        game_events.send(GameEvent::NextWave {
+            position: transform.translation.xy(),
+            velocity: velocity.0,
+        });
+
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: *collectible_position,
+            radius: 100.,
+            duration: Duration::from_secs_f32(1.),
+            color: Color::GREEN,
+            effect: shockwave::Effect::None,
+            falloff: shockwave::Falloff::Constant,
+            affects_player: false,
+            affects_collectibles: false,
+            source: None,
+        });
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn die(
-    player: Query<Entity, With<Player>>,
+    mut commands: Commands,
+    settings: Res<PlayerSettings>,
+    party: Res<Party>,
+    mode: Res<GameMode>,
+    mut players: Query<(Entity, &Player, &Transform, &mut Lives)>,
+    names: Query<&Name>,
     mut events: EventReader<health::Event>,
+    mut respawn_events: EventWriter<lives::Respawn>,
+    mut particle_events: EventWriter<particle::Event>,
+    mut cause: ResMut<CauseOfDeath>,
+    mut winner: ResMut<Winner>,
     mut gamestate: ResMut<NextState<crate::GameState>>,
 ) {
+    let mut last_damage = None;
+    let mut out = Vec::new();
     for event in events.read() {
-        let health::Event::Die(entity) = event;
-
-        let Ok(player) = player.get(*entity) else {
-            continue;
-        };
+        match event {
+            health::Event::Damaged {
+                target,
+                attacker,
+                kind,
+                ..
+            } if players.contains(*target) => {
+                let attacker = attacker
+                    .and_then(|attacker| names.get(attacker).ok())
+                    .map(ToString::to_string);
+                last_damage = Some((*kind, attacker));
+            }
+            health::Event::Die(entity) => {
+                let Ok((.., mut lives)) = players.get_mut(*entity) else {
+                    continue;
+                };
+
+                if !mode.rules().mortal {
+                    respawn_events.send(lives::Respawn(*entity));
+                } else if settings.respawn && lives.0 > 0 {
+                    lives.0 -= 1;
+                    respawn_events.send(lives::Respawn(*entity));
+                } else if !out.contains(entity) {
+                    out.push(*entity);
+                }
+            }
+            _ => {}
+        }
+    }
+    if out.is_empty() {
+        return;
+    }
 
-        if &player == entity {
-            gamestate.set(crate::GameState::GameOver);
+    let remaining = players
+        .iter()
+        .filter(|(entity, ..)| !out.contains(entity))
+        .map(|(_, player, ..)| player.slot)
+        .collect::<Vec<_>>();
+    if party.continues(remaining.len()) {
+        // Knocked out players leave the arena; the others play on.
+        for entity in out {
+            if let Ok((_, _, transform, _)) = players.get(entity) {
+                particle_events.send(death_burst(transform.translation.xy(), 240));
+            }
+            commands.entity(entity).despawn();
         }
+        return;
     }
+
+    cause.0 = last_damage.take();
+    if party.style == PlayStyle::Versus && party.players > 1 {
+        winner.0 = remaining.first().copied();
+    }
+    gamestate.set(crate::GameState::GameOver);
+}
+
+fn reset(mut cause: ResMut<CauseOfDeath>, mut winner: ResMut<Winner>) {
+    cause.0 = None;
+    winner.0 = None;
 }
 
 fn gameover(
     mut commands: Commands,
-    sounds: Res<Sounds>,
-    player: Query<(Entity, &Transform), With<Player>>,
+    players: Query<(Entity, &Transform), With<Player>>,
+    restart: Option<Res<crate::Restart>>,
     mut shockwave_events: EventWriter<shockwave::Event>,
-    mut rng: ResMut<RngSource>,
+    mut particle_events: EventWriter<particle::Event>,
+    mut audio_events: EventWriter<audio::Event>,
 ) {
-    let Ok((entity, transform)) = player.get_single() else {
-        return;
-    };
-
-    commands.entity(entity).despawn();
-
-    shockwave_events.send(shockwave::Event::Spawn {
-        position: transform.translation.xy(),
-        radius: 1000.,
-        duration: Duration::from_secs_f32(1.0),
-        color: Color::RED,
-        repel: true,
-    });
-    commands.spawn(AudioBundle {
-        source: sounds.gameover.iter().choose(&mut **rng).unwrap().clone(),
-        settings: PlaybackSettings {
-            mode: PlaybackMode::Remove,
-            ..default()
-        },
-    });
+    for (entity, transform) in &players {
+        commands.entity(entity).despawn();
+        if restart.is_some() {
+            continue;
+        }
+
+        particle_events.send(death_burst(transform.translation.xy(), 240));
+
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: transform.translation.xy(),
+            radius: 1000.,
+            duration: Duration::from_secs_f32(1.0),
+            color: Color::RED,
+            effect: shockwave::Effect::Repel,
+            falloff: shockwave::Falloff::Quadratic,
+            affects_player: false,
+            affects_collectibles: true,
+            source: None,
+        });
+        audio_events.send(audio::Event::Play {
+            group: SoundGroup::GameOver,
+            position: transform.translation.xy(),
+        });
+    }
 }
 
 fn engine_audio(
     settings: Res<BoidSettings>,
-    player: Query<(&AudioSink, &Velocity, &Boost), With<Player>>,
+    players: Query<(&SpatialAudioSink, &Velocity, &Boost), With<Player>>,
 ) {
-    let Ok((playback, vel, boost)) = player.get_single() else {
-        return;
-    };
-    playback.set_speed(vel.0.length() / (settings.max_speed * boost.multiplier - 0.5) * 2.);
+    for (playback, vel, boost) in &players {
+        playback.set_speed(vel.0.length() / (settings.top_speed() * boost.multiplier - 0.5) * 2.);
+    }
 }
 
-fn pause(player: Query<&AudioSink, With<Player>>) {
-    let Ok(playback) = player.get_single() else {
-        return;
-    };
-
-    playback.pause();
+fn pause(players: Query<&SpatialAudioSink, With<Player>>) {
+    for playback in &players {
+        playback.pause();
+    }
 }
 
-fn unpause(player: Query<&AudioSink, With<Player>>) {
-    let Ok(playback) = player.get_single() else {
-        return;
-    };
-
-    playback.play();
+fn unpause(players: Query<&SpatialAudioSink, With<Player>>) {
+    for playback in &players {
+        playback.play();
+    }
 }
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+pub const MAX_PLAYERS: usize = 4;
+
+const TINTS: [Color; MAX_PLAYERS] = [
+    Color::GREEN,
+    Color::rgb(0.3, 0.7, 1.0),
+    Color::ORANGE,
+    Color::rgb(1.0, 0.4, 0.9),
+];
+
+#[derive(Resource, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Party {
+    pub players: usize,
+    pub style: PlayStyle,
+}
+
+impl Default for Party {
+    fn default() -> Self {
+        Self {
+            players: 1,
+            style: PlayStyle::Coop,
+        }
+    }
+}
+
+impl Party {
+    pub fn continues(&self, remaining: usize) -> bool {
+        match self.style {
+            PlayStyle::Versus if self.players > 1 => remaining > 1,
+            PlayStyle::Coop | PlayStyle::Versus => remaining > 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PlayStyle {
+    Coop,
+    /// Boosts shove other players and the last one standing wins.
+    Versus,
+}
+
+impl PlayStyle {
+    pub const ALL: [PlayStyle; 2] = [PlayStyle::Coop, PlayStyle::Versus];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayStyle::Coop => "Co-op",
+            PlayStyle::Versus => "Versus",
+        }
+    }
+}
+
+pub fn tint(slot: usize) -> Color {
+    TINTS[slot % MAX_PLAYERS]
+}
+
+pub fn name(slot: usize) -> String {
+    format!("Player {}", slot + 1)
+}
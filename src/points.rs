@@ -1,34 +1,119 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 
+use crate::player::MAX_PLAYERS;
+
 pub struct PointsPlugin;
 
 impl Plugin for PointsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Points(0));
+        app.init_resource::<Scores>();
+        app.init_resource::<Breakdown>();
         app.add_event::<PointEvent>();
         app.add_systems(Update, collect.run_if(on_event::<PointEvent>()));
         app.add_systems(OnExit(crate::GameState::GameOver), reset);
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PointSource {
+    Collect,
+    Combo,
+    Boost,
+    Danger,
+    Doubler,
+    Alignment,
+    Survival,
+}
+
+impl PointSource {
+    pub const ALL: [PointSource; 7] = [
+        PointSource::Collect,
+        PointSource::Combo,
+        PointSource::Boost,
+        PointSource::Danger,
+        PointSource::Doubler,
+        PointSource::Alignment,
+        PointSource::Survival,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PointSource::Collect => "Collected",
+            PointSource::Combo => "Combo",
+            PointSource::Boost => "Boost",
+            PointSource::Danger => "Danger",
+            PointSource::Doubler => "Doubler",
+            PointSource::Alignment => "Alignment",
+            PointSource::Survival => "Survived",
+        }
+    }
+}
+
 #[derive(Debug, Event)]
 pub enum PointEvent {
-    Add(u32),
+    Add {
+        amount: u32,
+        source: PointSource,
+        position: Vec2,
+        /// Slot of the player who earned the points.
+        slot: usize,
+    },
     Remove(u32),
 }
 
 #[derive(Resource)]
 pub struct Points(pub u32);
 
-fn collect(mut read: EventReader<PointEvent>, mut points: ResMut<Points>) {
+#[derive(Resource, Default)]
+pub struct Scores(pub [u32; MAX_PLAYERS]);
+
+impl Scores {
+    pub fn best(&self) -> (usize, u32) {
+        self.0
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|&(slot, points)| (points, std::cmp::Reverse(slot)))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Resource, Default, Deref)]
+pub struct Breakdown(HashMap<PointSource, u32>);
+
+fn collect(
+    mut read: EventReader<PointEvent>,
+    mut points: ResMut<Points>,
+    mut scores: ResMut<Scores>,
+    mut breakdown: ResMut<Breakdown>,
+) {
     for event in read.read() {
         match event {
-            PointEvent::Add(amount) => points.0 += amount,
+            PointEvent::Add {
+                amount,
+                source,
+                slot,
+                ..
+            } => {
+                points.0 += amount;
+                scores.0[slot % MAX_PLAYERS] += amount;
+                *breakdown.0.entry(*source).or_default() += amount;
+            }
             PointEvent::Remove(amount) => points.0 -= amount,
         }
     }
 }
 
-fn reset(mut write: EventWriter<PointEvent>, points: Res<Points>) {
+fn reset(
+    mut write: EventWriter<PointEvent>,
+    points: Res<Points>,
+    mut scores: ResMut<Scores>,
+    mut breakdown: ResMut<Breakdown>,
+) {
     write.send(PointEvent::Remove(points.0));
+    *scores = Scores::default();
+    breakdown.0.clear();
 }
@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+
+use crate::{
+    health,
+    player::Player,
+    points::{PointEvent, PointSource},
+    scoring::Combo,
+    GameState,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, points.run_if(on_event::<PointEvent>()));
+        app.add_event::<Event>();
+        app.add_systems(Update, warnings.run_if(on_event::<Event>()));
+        app.add_systems(Update, health.run_if(on_event::<health::Event>()));
+        app.add_systems(Update, drift.run_if(not(in_state(GameState::Paused))));
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Warning { text: &'static str, position: Vec2 },
+}
+
+#[derive(Component)]
+struct Popup {
+    velocity: Vec2,
+    lifetime: f32,
+    remaining: f32,
+}
+
+impl Popup {
+    fn new(velocity: Vec2, lifetime: f32) -> Self {
+        Self {
+            velocity,
+            lifetime,
+            remaining: lifetime,
+        }
+    }
+}
+
+fn spawn(
+    commands: &mut Commands,
+    text: String,
+    size: f32,
+    color: Color,
+    position: Vec2,
+    popup: Popup,
+) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Popup"));
+    entity.insert(Text2dBundle {
+        text: Text::from_section(
+            text,
+            TextStyle {
+                font_size: size,
+                color,
+                ..default()
+            },
+        )
+        .with_alignment(TextAlignment::Center),
+        transform: Transform::from_translation(position.extend(20.0)),
+        ..default()
+    });
+    entity.insert(popup);
+}
+
+fn points(
+    mut commands: Commands,
+    mut events: EventReader<PointEvent>,
+    players: Query<(&Player, &Combo)>,
+) {
+    let mut totals: Vec<(Vec2, usize, u32, bool)> = Vec::new();
+    for event in events.read() {
+        let PointEvent::Add {
+            amount,
+            source,
+            position,
+            slot,
+        } = event
+        else {
+            continue;
+        };
+
+        let combo = *source == PointSource::Combo;
+        if let Some((.., total, has_combo)) = totals
+            .iter_mut()
+            .find(|(pos, other, ..)| pos == position && other == slot)
+        {
+            *total += amount;
+            *has_combo |= combo;
+        } else {
+            totals.push((*position, *slot, *amount, combo));
+        }
+    }
+
+    for (position, slot, total, has_combo) in totals {
+        spawn(
+            &mut commands,
+            format!("+{total}"),
+            28.0,
+            Color::WHITE,
+            position,
+            Popup::new(Vec2::Y * 40.0, 1.0),
+        );
+
+        let combo = players
+            .iter()
+            .find(|(player, _)| player.slot() == slot)
+            .map_or(0, |(_, combo)| combo.chain);
+        if has_combo && combo > 1 {
+            spawn(
+                &mut commands,
+                format!("x{combo} combo"),
+                20.0,
+                Color::YELLOW,
+                position + Vec2::Y * 24.0,
+                Popup::new(Vec2::Y * 60.0, 1.2),
+            );
+        }
+    }
+}
+
+fn warnings(mut commands: Commands, mut events: EventReader<Event>) {
+    for event in events.read() {
+        let Event::Warning { text, position } = event;
+        spawn(
+            &mut commands,
+            (*text).to_string(),
+            20.0,
+            Color::RED,
+            *position + Vec2::Y * 16.0,
+            Popup::new(Vec2::Y * 20.0, 1.5),
+        );
+    }
+}
+
+fn health(
+    mut commands: Commands,
+    mut events: EventReader<health::Event>,
+    targets: Query<&Transform>,
+) {
+    for event in events.read() {
+        let (target, text, color) = match event {
+            health::Event::Damaged { target, amount, .. } => {
+                (target, format!("-{amount}"), Color::RED)
+            }
+            health::Event::Healed { target, amount } => {
+                (target, format!("+{amount} HP"), Color::GREEN)
+            }
+            health::Event::Die(_) => continue,
+        };
+        let Ok(transform) = targets.get(*target) else {
+            continue;
+        };
+
+        spawn(
+            &mut commands,
+            text,
+            24.0,
+            color,
+            transform.translation.xy() + Vec2::Y * 24.0,
+            Popup::new(Vec2::Y * 30.0, 1.0),
+        );
+    }
+}
+
+fn drift(
+    mut commands: Commands,
+    mut popups: Query<(Entity, &mut Popup, &mut Transform, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut popup, mut transform, mut text) in &mut popups {
+        popup.remaining -= time.delta_seconds();
+        if popup.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += popup.velocity.extend(0.0) * time.delta_seconds();
+        let alpha = popup.remaining / popup.lifetime;
+        for section in &mut text.sections {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+fn reset(mut commands: Commands, popups: Query<Entity, With<Popup>>) {
+    for entity in &popups {
+        commands.entity(entity).despawn();
+    }
+}
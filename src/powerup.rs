@@ -0,0 +1,236 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    collectible::{self, Collectible, CollectibleKind},
+    health::Invulnerable,
+    player::Player,
+    rng::RngSource,
+    shockwave, GameState,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.insert_resource(PowerUpSettings {
+            min_interval: 10.0,
+            max_interval: 20.0,
+            duration: 8.0,
+            lifetime: 8.0,
+            magnet_range: 250.0,
+            magnet_speed: 300.0,
+            slow_mo: 0.5,
+            doubler: 2.0,
+        });
+        app.insert_resource(SpawnTimer(15.0));
+        app.add_systems(Update, spawn.run_if(in_state(GameState::Playing)));
+        app.add_systems(Update, activate.run_if(on_event::<Event>()));
+        app.add_systems(Update, (tick, magnet).run_if(in_state(GameState::Playing)));
+        app.add_systems(Update, slow_mo);
+        app.add_systems(OnExit(GameState::GameOver), reset);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUp {
+    Shield,
+    Magnet,
+    SlowMo,
+    Doubler,
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 4] = [
+        PowerUp::Shield,
+        PowerUp::Magnet,
+        PowerUp::SlowMo,
+        PowerUp::Doubler,
+    ];
+
+    pub fn color(self) -> Color {
+        match self {
+            PowerUp::Shield => Color::CYAN,
+            PowerUp::Magnet => Color::PURPLE,
+            PowerUp::SlowMo => Color::ALICE_BLUE,
+            PowerUp::Doubler => Color::GOLD,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            PowerUp::Shield => "S",
+            PowerUp::Magnet => "M",
+            PowerUp::SlowMo => "T",
+            PowerUp::Doubler => "2x",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUp::Shield => "Shield",
+            PowerUp::Magnet => "Magnet",
+            PowerUp::SlowMo => "Slow-mo",
+            PowerUp::Doubler => "Doubler",
+        }
+    }
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Activate { target: Entity, power_up: PowerUp },
+}
+
+#[derive(Resource)]
+pub struct PowerUpSettings {
+    pub min_interval: f32,
+    pub max_interval: f32,
+    pub duration: f32,
+    pub lifetime: f32,
+    pub magnet_range: f32,
+    pub magnet_speed: f32,
+    pub slow_mo: f32,
+    pub doubler: f32,
+}
+
+#[derive(Resource)]
+struct SpawnTimer(f32);
+
+#[derive(Component, Default, Deref)]
+pub struct PowerUps(HashMap<PowerUp, f32>);
+
+impl PowerUps {
+    pub fn active(&self, power_up: PowerUp) -> bool {
+        self.0.contains_key(&power_up)
+    }
+}
+
+fn spawn(
+    settings: Res<PowerUpSettings>,
+    mut timer: ResMut<SpawnTimer>,
+    mut rng: ResMut<RngSource>,
+    mut events: EventWriter<collectible::Event>,
+    time: Res<Time>,
+) {
+    timer.0 -= time.delta_seconds();
+    if timer.0 > 0.0 {
+        return;
+    }
+
+    timer.0 = rng.gen_range(settings.min_interval..=settings.max_interval);
+    if let Some(power_up) = PowerUp::ALL.choose(&mut **rng) {
+        events.send(collectible::Event::Spawn(CollectibleKind::PowerUp(
+            *power_up,
+        )));
+    }
+}
+
+fn activate(
+    mut commands: Commands,
+    settings: Res<PowerUpSettings>,
+    mut targets: Query<(&Transform, Option<&mut PowerUps>)>,
+    mut events: EventReader<Event>,
+    mut shockwave_events: EventWriter<shockwave::Event>,
+) {
+    for event in events.read() {
+        let Event::Activate { target, power_up } = event;
+        let Ok((transform, power_ups)) = targets.get_mut(*target) else {
+            continue;
+        };
+
+        if let Some(mut power_ups) = power_ups {
+            power_ups.0.insert(*power_up, settings.duration);
+        } else {
+            let mut power_ups = PowerUps::default();
+            power_ups.0.insert(*power_up, settings.duration);
+            commands.entity(*target).insert(power_ups);
+        }
+
+        let (effect, falloff, radius) = match power_up {
+            PowerUp::Shield => {
+                commands
+                    .entity(*target)
+                    .insert(Invulnerable(settings.duration));
+                continue;
+            }
+            PowerUp::Magnet => (
+                shockwave::Effect::Attract,
+                shockwave::Falloff::Smooth,
+                settings.magnet_range,
+            ),
+            PowerUp::SlowMo | PowerUp::Doubler => continue,
+        };
+        shockwave_events.send(shockwave::Event::Spawn {
+            position: transform.translation.xy(),
+            radius,
+            duration: Duration::from_secs_f32(0.5),
+            color: power_up.color(),
+            effect,
+            falloff,
+            affects_player: false,
+            affects_collectibles: true,
+            source: None,
+        });
+    }
+}
+
+fn tick(mut power_ups: Query<&mut PowerUps>, time: Res<Time>) {
+    for mut power_ups in &mut power_ups {
+        for remaining in power_ups.0.values_mut() {
+            *remaining -= time.delta_seconds();
+        }
+        power_ups.0.retain(|_, remaining| *remaining > 0.0);
+    }
+}
+
+fn magnet(
+    settings: Res<PowerUpSettings>,
+    players: Query<(&Transform, &PowerUps), With<Player>>,
+    mut collectibles: Query<(&mut Transform, &Collectible), Without<Player>>,
+    time: Res<Time>,
+) {
+    for (player, power_ups) in &players {
+        if !power_ups.active(PowerUp::Magnet) {
+            continue;
+        }
+
+        let target = player.translation.xy();
+        for (mut transform, collectible) in &mut collectibles {
+            if collectible.kind != CollectibleKind::Points {
+                continue;
+            }
+
+            let offset = target - transform.translation.xy();
+            if offset.length_squared() > settings.magnet_range * settings.magnet_range {
+                continue;
+            }
+            let step = offset.clamp_length_max(settings.magnet_speed * time.delta_seconds());
+            transform.translation += step.extend(0.0);
+        }
+    }
+}
+
+fn slow_mo(
+    settings: Res<PowerUpSettings>,
+    state: Res<State<GameState>>,
+    power_ups: Query<&PowerUps, With<Player>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let slowed = *state.get() == GameState::Playing
+        && power_ups
+            .iter()
+            .any(|power_ups| power_ups.active(PowerUp::SlowMo));
+    let speed = if slowed { settings.slow_mo } else { 1.0 };
+
+    #[allow(clippy::float_cmp)]
+    if time.relative_speed() != speed {
+        time.set_relative_speed(speed);
+    }
+}
+
+fn reset(settings: Res<PowerUpSettings>, mut timer: ResMut<SpawnTimer>) {
+    timer.0 = (settings.min_interval + settings.max_interval) * 0.5;
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use rand::{rngs::SmallRng, SeedableRng};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 pub struct RngPlugin;
 
@@ -9,11 +10,18 @@ impl Plugin for RngPlugin {
     }
 }
 
+/// Shared game randomness; `ChaCha8` so a seed plays out the same on every platform.
 #[derive(Deref, DerefMut, Resource)]
-pub struct RngSource(SmallRng);
+pub struct RngSource(ChaCha8Rng);
+
+impl RngSource {
+    pub fn seeded(seed: u64) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed))
+    }
+}
 
 impl Default for RngSource {
     fn default() -> Self {
-        Self(SmallRng::from_entropy())
+        Self(ChaCha8Rng::from_entropy())
     }
 }
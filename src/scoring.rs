@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use bevy_spatial::kdtree::KDTree2;
+
+use crate::{
+    arena::Arena,
+    boid::{AngryBoi, Boid},
+    player::{Boost, Player},
+    points::{PointEvent, PointSource},
+    powerup::{PowerUp, PowerUpSettings, PowerUps},
+    track::Tracked,
+    velocity::Velocity,
+    GameState,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Event>();
+        app.insert_resource(ScoringRules {
+            combo_window: 3.0,
+            combo_step: 0.5,
+            max_combo: 5.0,
+            boost_multiplier: 2.0,
+            danger_range: 100.0,
+            danger_step: 0.25,
+            max_danger: 2.0,
+            alignment_range: 100.0,
+            alignment_threshold: 0.9,
+            alignment_bonus: 1,
+        });
+        app.add_systems(
+            Update,
+            (multiplier, collect.run_if(on_event::<Event>()))
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+        app.add_systems(Update, combo.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Debug, Event)]
+pub enum Event {
+    Collect {
+        value: u32,
+        position: Vec2,
+        /// The player who collected.
+        player: Entity,
+    },
+}
+
+#[derive(Resource)]
+pub struct ScoringRules {
+    pub combo_window: f32,
+    pub combo_step: f32,
+    pub max_combo: f32,
+    pub boost_multiplier: f32,
+    pub danger_range: f32,
+    pub danger_step: f32,
+    pub max_danger: f32,
+    pub alignment_range: f32,
+    pub alignment_threshold: f32,
+    pub alignment_bonus: u32,
+}
+
+#[derive(Component, Default)]
+pub struct Combo {
+    pub chain: u32,
+    remaining: f32,
+}
+
+#[derive(Component, PartialEq)]
+pub struct Multiplier {
+    pub combo: f32,
+    pub boost: f32,
+    pub danger: f32,
+    pub doubler: f32,
+}
+
+impl Default for Multiplier {
+    fn default() -> Self {
+        Self {
+            combo: 1.0,
+            boost: 1.0,
+            danger: 1.0,
+            doubler: 1.0,
+        }
+    }
+}
+
+impl Multiplier {
+    pub fn total(&self) -> f32 {
+        self.combo * self.boost * self.danger * self.doubler
+    }
+}
+
+impl ScoringRules {
+    #[allow(clippy::cast_precision_loss)]
+    fn combo(&self, chain: u32) -> f32 {
+        (1.0 + chain.saturating_sub(1) as f32 * self.combo_step).min(self.max_combo)
+    }
+}
+
+fn combo(mut players: Query<(&mut Combo, &mut Multiplier)>, time: Res<Time>) {
+    for (mut combo, mut multiplier) in &mut players {
+        if combo.chain == 0 {
+            continue;
+        }
+
+        combo.remaining -= time.delta_seconds();
+        if combo.remaining <= 0.0 {
+            combo.chain = 0;
+            multiplier.combo = 1.0;
+        }
+    }
+}
+
+fn multiplier(
+    rules: Res<ScoringRules>,
+    power_up_settings: Res<PowerUpSettings>,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    mut players: Query<(&Transform, &Boost, Option<&PowerUps>, &mut Multiplier), With<Player>>,
+    angry: Query<(), With<AngryBoi>>,
+) {
+    for (transform, boost, power_ups, mut multiplier) in &mut players {
+        let threats = arena
+            .within_distance(&quadtree, transform.translation.xy(), rules.danger_range)
+            .into_iter()
+            .filter_map(|(_pos, entity)| entity)
+            .filter(|entity| angry.contains(*entity))
+            .count();
+
+        #[allow(clippy::cast_precision_loss)]
+        let danger = (1.0 + threats as f32 * rules.danger_step).min(rules.max_danger);
+        let boost = if boost.active() {
+            rules.boost_multiplier
+        } else {
+            1.0
+        };
+
+        let doubler = if power_ups.is_some_and(|power_ups| power_ups.active(PowerUp::Doubler)) {
+            power_up_settings.doubler
+        } else {
+            1.0
+        };
+
+        multiplier.set_if_neq(Multiplier {
+            boost,
+            danger,
+            doubler,
+            ..*multiplier
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn collect(
+    rules: Res<ScoringRules>,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    mut players: Query<(&Player, &Velocity, &mut Combo, &mut Multiplier)>,
+    boids: Query<&Velocity, With<Boid>>,
+    mut events: EventReader<Event>,
+    mut points: EventWriter<PointEvent>,
+) {
+    for event in events.read() {
+        let Event::Collect {
+            value,
+            position,
+            player,
+        } = event;
+        let Ok((player, vel, mut combo, mut multiplier)) = players.get_mut(*player) else {
+            continue;
+        };
+        let heading = vel.normalize_or_zero();
+
+        combo.chain += 1;
+        combo.remaining = rules.combo_window;
+        multiplier.combo = rules.combo(combo.chain);
+
+        #[allow(clippy::cast_precision_loss)]
+        let base = *value as f32;
+        let combo_total = base * multiplier.combo;
+        let boost_total = combo_total * multiplier.boost;
+        let danger_total = boost_total * multiplier.danger;
+        let doubler_total = danger_total * multiplier.doubler;
+
+        let aligned = arena
+            .within_distance(&quadtree, *position, rules.alignment_range)
+            .into_iter()
+            .filter_map(|(_pos, entity)| entity)
+            .filter_map(|entity| boids.get(entity).ok())
+            .filter(|vel| vel.normalize_or_zero().dot(heading) >= rules.alignment_threshold)
+            .count();
+        #[allow(clippy::cast_precision_loss)]
+        let alignment_total = u32::try_from(aligned)
+            .unwrap_or(u32::MAX)
+            .saturating_mul(rules.alignment_bonus) as f32;
+
+        for (source, amount) in [
+            (PointSource::Collect, base),
+            (PointSource::Combo, combo_total - base),
+            (PointSource::Boost, boost_total - combo_total),
+            (PointSource::Danger, danger_total - boost_total),
+            (PointSource::Doubler, doubler_total - danger_total),
+            (PointSource::Alignment, alignment_total),
+        ] {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let amount = amount.round() as u32;
+            if amount > 0 {
+                points.send(PointEvent::Add {
+                    amount,
+                    source,
+                    position: *position,
+                    slot: player.slot(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn combo() {
+        let rules = ScoringRules {
+            combo_window: 3.0,
+            combo_step: 0.5,
+            max_combo: 2.0,
+            boost_multiplier: 2.0,
+            danger_range: 100.0,
+            danger_step: 0.25,
+            max_danger: 2.0,
+            alignment_range: 100.0,
+            alignment_threshold: 0.9,
+            alignment_bonus: 1,
+        };
+        assert_eq!(rules.combo(0), 1.0);
+        assert_eq!(rules.combo(1), 1.0);
+        assert_eq!(rules.combo(2), 1.5);
+        assert_eq!(rules.combo(3), 2.0);
+        assert_eq!(rules.combo(10), 2.0);
+    }
+}
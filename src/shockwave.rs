@@ -1,20 +1,27 @@
-use std::{f32::consts::PI, time::Duration};
+use std::time::Duration;
 
 use bevy::prelude::*;
-use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
-use interpolation::{Ease, Lerp};
-use rand::Rng;
+use bevy_spatial::kdtree::KDTree2;
+use interpolation::{Ease, EaseFunction, Lerp};
 
-use crate::{assets::Images, player::Player, rng::RngSource, track::Tracked, velocity::Velocity};
+use crate::{
+    arena::Arena,
+    boid::{self, AngryBoi, BoidKind},
+    collectible::Collectible,
+    health::{self, DamageKind},
+    particle::{Emitter, ParticleStyle},
+    player::Player,
+    track::Tracked,
+    velocity::Velocity,
+};
 
 pub struct Plugin;
 
 impl bevy::prelude::Plugin for Plugin {
     fn build(&self, app: &mut App) {
         app.add_event::<Event>();
-        app.add_systems(Update, avoid);
+        app.add_systems(Update, (affect, stun));
         app.add_systems(Update, expiration);
-        app.add_systems(Update, smoke);
         app.add_systems(Update, spawn.run_if(on_event::<Event>()));
     }
 }
@@ -26,44 +33,83 @@ pub enum Event {
         radius: f32,
         duration: Duration,
         color: Color,
-        repel: bool,
+        effect: Effect,
+        falloff: Falloff,
+        affects_player: bool,
+        affects_collectibles: bool,
+        /// Left alone by its own shockwave.
+        source: Option<Entity>,
     },
 }
 
-#[derive(Component)]
-struct Repel;
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Effect {
+    None,
+    Repel,
+    Attract,
+    Stun(f32),
+    Convert,
+    Damage(u32),
+}
 
-#[derive(Component)]
-struct Smoke {
-    destination_offset: Vec3,
-    scale: f32,
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Falloff {
+    Constant,
+    Linear,
+    Quadratic,
+    Smooth,
+}
+
+impl Falloff {
+    /// Strength at `t`, the distance from the center as a fraction of the radius.
+    pub fn strength(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Falloff::Constant => 1.0,
+            Falloff::Linear => 1.0 - t,
+            Falloff::Quadratic => (1.0 - t) * (1.0 - t),
+            Falloff::Smooth => 1.0 - t * t * (3.0 - 2.0 * t),
+        }
+    }
 }
 
+#[derive(Component)]
+pub struct Stunned(pub f32);
+
+const STUN_SPEED: f32 = 1.0;
+
 #[derive(Component)]
 struct Shockwave {
     duration: f32,
     remaining: f32,
     max_radius: f32,
     active_radius: f32,
+    effect: Effect,
+    falloff: Falloff,
+    affects_player: bool,
+    affects_collectibles: bool,
+    source: Option<Entity>,
+    hit: Vec<Entity>,
 }
 
 impl Shockwave {
-    fn new(duration: Duration, radius: f32) -> Self {
+    fn new(duration: Duration, radius: f32, effect: Effect, falloff: Falloff) -> Self {
         Self {
             duration: duration.as_secs_f32(),
             remaining: duration.as_secs_f32(),
             max_radius: radius,
             active_radius: 0.0,
+            effect,
+            falloff,
+            affects_player: false,
+            affects_collectibles: false,
+            source: None,
+            hit: Vec::new(),
         }
     }
 }
 
-fn spawn(
-    mut commands: Commands,
-    images: Res<Images>,
-    mut events: EventReader<Event>,
-    mut rng: ResMut<RngSource>,
-) {
+fn spawn(mut commands: Commands, mut events: EventReader<Event>) {
     for event in events.read() {
         match event {
             Event::Spawn {
@@ -71,54 +117,43 @@ fn spawn(
                 radius,
                 duration,
                 color,
-                repel,
+                effect,
+                falloff,
+                affects_player,
+                affects_collectibles,
+                source,
             } => {
                 assert!(radius > &0.0);
                 let mut entity = commands.spawn_empty();
                 entity.insert(Name::new("Shockwave"));
-                entity.insert(Shockwave::new(*duration, *radius));
+                entity.insert(Shockwave {
+                    affects_player: *affects_player,
+                    affects_collectibles: *affects_collectibles,
+                    source: *source,
+                    ..Shockwave::new(*duration, *radius, *effect, *falloff)
+                });
                 entity.insert(TransformBundle::from_transform(
                     Transform::from_translation(center.extend(0.0)),
                 ));
-                if *repel {
-                    entity.insert(Repel);
-                }
 
-                entity.with_children(|parent| {
-                    let density = radius.floor();
-                    #[allow(clippy::cast_possible_truncation)]
-                    let count = density.floor() as i16;
-                    for i in 0..count {
-                        let offset = rng.gen::<f32>().elastic_out();
-                        let angle = (f32::from(i) / density) * PI * 2.0;
-                        let scale = rng.gen::<f32>() * 0.5;
-                        let mut smoke = parent.spawn_empty();
-                        smoke.insert(Name::new("Smoke"));
-                        smoke.insert(Smoke {
-                            destination_offset: Vec3 {
-                                x: angle.cos(),
-                                y: angle.sin(),
-                                z: 0.0,
-                            } * offset,
-                            scale,
-                        });
-                        smoke.insert(SpriteBundle {
-                            sprite: Sprite {
-                                color: *color,
-                                ..default()
-                            },
-                            texture: images.smoke.clone(),
-                            transform: Transform {
-                                scale: Vec3::ONE * scale,
-                                rotation: Quat::from_axis_angle(
-                                    Vec3::Z,
-                                    rng.gen::<f32>() * PI * 2.,
-                                ),
-                                ..default()
-                            },
-                            ..default()
-                        });
-                    }
+                let travel = (radius - 32.0).max(0.0);
+                let seconds = duration.as_secs_f32();
+                let drag = 3.0 / seconds;
+                let speed = travel * drag * 1.15;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let count = radius.floor() as u32;
+                entity.insert(Emitter {
+                    burst: count,
+                    lifetime: seconds..seconds,
+                    speed: speed * 0.2..speed,
+                    radius: 16.0,
+                    size: 4.0..32.0 * seconds,
+                    spin: 10.0,
+                    ..Emitter::new(
+                        ParticleStyle::new(&[*color])
+                            .with_scale(1.0..0.0, EaseFunction::QuadraticOut)
+                            .with_drag(drag),
+                    )
                 });
             }
         }
@@ -140,36 +175,103 @@ fn expiration(
     }
 }
 
-fn smoke(
-    mut smoke: Query<(&Parent, &mut Transform, &Smoke)>,
-    shockwaves: Query<&Shockwave>,
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn affect(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    quadtree: Res<KDTree2<Tracked>>,
+    mut shockwaves: Query<(&Transform, &mut Shockwave)>,
+    mut movers: Query<(&mut Velocity, Has<Player>, Has<AngryBoi>), Without<Collectible>>,
+    mut collectibles: Query<&mut Transform, (With<Collectible>, Without<Shockwave>)>,
+    mut damage: EventWriter<health::Damage>,
+    mut spawn: EventWriter<boid::SpawnEvent>,
     time: Res<Time>,
 ) {
-    for (parent, mut transform, smoke) in &mut smoke {
-        let shockwave = shockwaves.get(parent.get()).unwrap();
-        transform.translation = smoke.destination_offset * shockwave.active_radius;
-        transform.rotation *=
-            Quat::from_axis_angle(Vec3::Z, time.delta_seconds() * 20.0 * shockwave.remaining);
-        transform.scale = Vec3::ONE * smoke.scale * shockwave.remaining;
-    }
-}
+    for (shockwave_trans, mut shockwave) in &mut shockwaves {
+        if shockwave.effect == Effect::None {
+            continue;
+        }
 
-fn avoid(
-    quadtree: Res<KDTree2<Tracked>>,
-    mut boids: Query<&mut Velocity, Without<Player>>,
-    shockwaves: Query<(&Transform, &Shockwave), With<Repel>>,
-) {
-    for (shockwave_trans, shockwave) in shockwaves.iter() {
         let shock_pos = shockwave_trans.translation.xy();
-        for (boid_pos, boid_entity) in quadtree
-            .within_distance(shock_pos, shockwave.active_radius)
+        let radius = shockwave.active_radius;
+        let source = shockwave.source;
+        for (pos, entity) in arena
+            .within_distance(&quadtree, shock_pos, radius)
             .into_iter()
             .filter_map(|(pos, entity)| entity.map(|entity| (pos, entity)))
+            .filter(|(_pos, entity)| Some(*entity) != source)
         {
-            let force = shockwave.active_radius - (boid_pos - shock_pos).length();
-            if let Ok(mut vel) = boids.get_mut(boid_entity) {
-                vel.0 -= (shock_pos - boid_pos).normalize_or_zero() * force;
+            let offset = arena.delta(shock_pos, pos);
+            let strength = shockwave.falloff.strength(offset.length() / radius);
+            let push = offset.normalize_or_zero() * radius * strength;
+
+            if let Ok(mut transform) = collectibles.get_mut(entity) {
+                if !shockwave.affects_collectibles {
+                    continue;
+                }
+                let step = (push * time.delta_seconds()).extend(0.0);
+                match shockwave.effect {
+                    Effect::Repel => transform.translation += step,
+                    Effect::Attract => transform.translation -= step,
+                    Effect::None | Effect::Stun(_) | Effect::Convert | Effect::Damage(_) => {}
+                }
+                continue;
             }
+
+            let Ok((mut vel, is_player, is_angry)) = movers.get_mut(entity) else {
+                continue;
+            };
+            if is_player && !shockwave.affects_player {
+                continue;
+            }
+            match shockwave.effect {
+                Effect::None => {}
+                Effect::Repel => vel.0 += push,
+                Effect::Attract => vel.0 -= push,
+                Effect::Stun(_) | Effect::Convert | Effect::Damage(_)
+                    if shockwave.hit.contains(&entity) => {}
+                Effect::Stun(seconds) => {
+                    vel.0 = vel.normalize_or_zero() * STUN_SPEED;
+                    commands.entity(entity).insert(Stunned(seconds * strength));
+                    shockwave.hit.push(entity);
+                }
+                Effect::Convert => {
+                    if is_angry {
+                        commands.entity(entity).despawn_recursive();
+                        spawn.send(boid::SpawnEvent {
+                            kind: BoidKind::Boi,
+                            count: 1,
+                            position: pos,
+                            velocity: vel.0,
+                        });
+                    }
+                    shockwave.hit.push(entity);
+                }
+                Effect::Damage(amount) => {
+                    #[allow(
+                        clippy::cast_possible_truncation,
+                        clippy::cast_sign_loss,
+                        clippy::cast_precision_loss
+                    )]
+                    let amount = ((amount as f32 * strength).ceil() as u32).max(1);
+                    damage.send(health::Damage {
+                        target: entity,
+                        attacker: source,
+                        amount,
+                        kind: DamageKind::Shockwave,
+                    });
+                    shockwave.hit.push(entity);
+                }
+            }
+        }
+    }
+}
+
+fn stun(mut commands: Commands, mut stunned: Query<(Entity, &mut Stunned)>, time: Res<Time>) {
+    for (entity, mut stunned) in &mut stunned {
+        stunned.0 -= time.delta_seconds();
+        if stunned.0 <= 0.0 {
+            commands.entity(entity).remove::<Stunned>();
         }
     }
 }
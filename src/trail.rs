@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology, view::NoFrustumCulling},
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    transform::TransformSystem,
+};
+
+use crate::{
+    boid::{Boid, BoidSettings},
+    velocity::Velocity,
+    GameState,
+};
+
+pub struct Plugin;
+
+impl bevy::prelude::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TrailSettings {
+            player_length: 40,
+            boid_length: 6,
+            spacing: 6.0,
+            boids: true,
+            max_boids: 300,
+            teleport: 120.0,
+            max_width: 3.0,
+        });
+        app.add_systems(Startup, setup);
+        app.add_systems(
+            PostUpdate,
+            (record.run_if(not(in_state(GameState::Paused))), render)
+                .chain()
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct TrailSettings {
+    pub player_length: usize,
+    pub boid_length: usize,
+    pub spacing: f32,
+    pub boids: bool,
+    pub max_boids: usize,
+    /// A jump longer than this between frames is treated as a teleport and clears the trail.
+    pub teleport: f32,
+    pub max_width: f32,
+}
+
+#[derive(Component)]
+pub struct Trail {
+    pub color: Color,
+    pub width: f32,
+    /// Newest first.
+    points: VecDeque<(Vec2, f32)>,
+}
+
+impl Trail {
+    pub fn new(color: Color, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            points: VecDeque::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+
+#[derive(Component)]
+struct TrailMesh;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Trails"));
+    entity.insert(TrailMesh);
+    entity.insert(NoFrustumCulling);
+    entity.insert(MaterialMesh2dBundle {
+        mesh: meshes
+            .add(Mesh::new(PrimitiveTopology::TriangleList))
+            .into(),
+        material: materials.add(ColorMaterial::from(Color::WHITE)),
+        transform: Transform::from_xyz(0.0, 0.0, -1.0),
+        visibility: Visibility::Hidden,
+        ..default()
+    });
+}
+
+fn record(
+    settings: Res<TrailSettings>,
+    boid_settings: Res<BoidSettings>,
+    mut trails: Query<(&Transform, &Velocity, &mut Trail, Has<Boid>)>,
+    boids: Query<(), With<Boid>>,
+) {
+    let boids_enabled = settings.boids && boids.iter().count() <= settings.max_boids;
+    for (transform, vel, mut trail, is_boid) in &mut trails {
+        let length = if is_boid {
+            if !boids_enabled {
+                trail.clear();
+                continue;
+            }
+            settings.boid_length
+        } else {
+            settings.player_length
+        };
+
+        let position = transform.translation.xy();
+        let width = trail.width
+            * (vel.length() / boid_settings.top_speed().max(f32::EPSILON)).min(settings.max_width);
+        match trail.points.front() {
+            Some((last, _)) if last.distance(position) > settings.teleport => trail.clear(),
+            Some((last, _)) if last.distance(position) < settings.spacing => {
+                trail.points[0].1 = width;
+                continue;
+            }
+            _ => {}
+        }
+        trail.points.push_front((position, width));
+        trail.points.truncate(length);
+    }
+}
+
+fn render(
+    trails: Query<(&Transform, &Trail)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh: Query<(&Mesh2dHandle, &mut Visibility), With<TrailMesh>>,
+) {
+    let Ok((handle, mut visibility)) = mesh.get_single_mut() else {
+        return;
+    };
+
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    for (transform, trail) in &trails {
+        if trail.points.is_empty() {
+            continue;
+        }
+
+        // The head always sits on the entity, even between recorded points.
+        let head = (transform.translation.xy(), trail.points[0].1);
+        let points = std::iter::once(head)
+            .chain(trail.points.iter().copied())
+            .collect::<Vec<_>>();
+        let color = Vec4::from(trail.color.as_linear_rgba_f32());
+        #[allow(clippy::cast_precision_loss)]
+        let last = (points.len() - 1).max(1) as f32;
+        for (i, &(point, width)) in points.iter().enumerate() {
+            let before = points[i.saturating_sub(1)].0;
+            let after = points[(i + 1).min(points.len() - 1)].0;
+            let side = (before - after).normalize_or_zero().perp();
+            #[allow(clippy::cast_precision_loss)]
+            let fade = 1.0 - i as f32 / last;
+            let offset = side * width * fade * 0.5;
+            let color = (color * Vec4::new(1.0, 1.0, 1.0, fade)).to_array();
+
+            #[allow(clippy::cast_possible_truncation)]
+            let base = positions.len() as u32;
+            positions.push((point + offset).extend(0.0).to_array());
+            positions.push((point - offset).extend(0.0).to_array());
+            colors.extend([color, color]);
+            if i > 0 {
+                indices.extend([base - 2, base - 1, base, base - 1, base + 1, base]);
+            }
+        }
+    }
+
+    if indices.is_empty() {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    let Some(mesh) = meshes.get_mut(&handle.0) else {
+        return;
+    };
+    *visibility = Visibility::Inherited;
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.set_indices(Some(Indices::U32(indices)));
+}
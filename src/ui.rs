@@ -1,25 +1,63 @@
 use bevy::{prelude::*, sprite::Anchor};
 
 use crate::{
+    arena::Arena,
+    assets::Images,
     boid::{BoidKind, SpawnEvent},
-    points::Points,
-    GameState,
+    camera::ShakeSettings,
+    daily::DailyChallenge,
+    difficulty::{Difficulty, DifficultySettings},
+    health::Health,
+    mode::{self, GameMode, HighScores},
+    net::{Disconnected, Session},
+    player::{self, CauseOfDeath, Lives, Party, PlayStyle, Player, Winner, MAX_PLAYERS},
+    points::{Breakdown, PointSource, Points, Scores},
+    powerup::{PowerUp, PowerUps},
+    scoring::Multiplier,
+    GameState, Restart,
 };
 
 pub struct Plugin;
 
 impl bevy::prelude::Plugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup);
+        app.add_systems(Startup, (setup, setup_entity_count, setup_health));
+        app.add_systems(Update, update_health);
+        app.add_systems(Startup, setup_power_ups);
+        app.add_systems(Update, update_power_ups);
         app.add_systems(Update, update_points.run_if(resource_changed::<Points>()));
+        app.add_systems(Update, update_multiplier);
         app.add_systems(Update, update_entity_count.run_if(on_event::<SpawnEvent>()));
+        app.add_systems(
+            Update,
+            place_entity_count.run_if(resource_changed::<Arena>()),
+        );
         app.add_systems(OnEnter(GameState::Paused), show_menu);
         app.add_systems(OnExit(GameState::Paused), hide_menu);
-        app.add_systems(OnEnter(GameState::GameOver), gameover);
+        app.add_systems(OnEnter(GameState::Connecting), show_connecting);
+        app.add_systems(OnExit(GameState::Connecting), hide_connecting);
+        app.add_systems(
+            Update,
+            update_menu.run_if(
+                resource_changed::<ShakeSettings>()
+                    .or_else(resource_changed::<DifficultySettings>()),
+            ),
+        );
+        app.add_systems(Update, update_mode);
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            gameover
+                .after(mode::record)
+                .run_if(not(resource_exists::<Restart>())),
+        );
         app.add_systems(OnExit(GameState::GameOver), reset);
         app.add_systems(
             Update,
-            try_again_button.run_if(in_state(GameState::GameOver)),
+            (try_again_button, party_buttons).run_if(in_state(GameState::GameOver)),
+        );
+        app.add_systems(
+            Update,
+            mode_buttons.run_if(in_state(GameState::GameOver).or_else(in_state(GameState::Paused))),
         );
     }
 }
@@ -86,6 +124,31 @@ fn setup(mut commands: Commands) {
                             .with_text_alignment(TextAlignment::Center),
                         )
                         .insert(PointText);
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 24.0,
+                                    ..default()
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center),
+                        )
+                        .insert(MultiplierText);
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 20.0,
+                                    color: Color::GRAY,
+                                    ..default()
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center),
+                        )
+                        .insert(ModeText);
                 });
 
             // Pause menu
@@ -106,7 +169,9 @@ fn setup(mut commands: Commands) {
                 ..default()
             });
         });
+}
 
+fn setup_entity_count(mut commands: Commands, arena: Res<Arena>) {
     let mut boids = commands.spawn_empty();
     boids.insert(Name::new("Boid Count"));
     boids.with_children(|parent| {
@@ -129,7 +194,7 @@ fn setup(mut commands: Commands) {
                 ),
             ]),
             text_anchor: Anchor::BottomLeft,
-            global_transform: GlobalTransform::from_xyz(-499., -299., 10.0),
+            global_transform: entity_count_transform(&arena),
             ..default()
         });
         entity.insert(EntityCount(0));
@@ -145,9 +210,74 @@ fn update_points(mut text: Query<&mut Text, With<PointText>>, points: Res<Points
     }
 }
 
+#[derive(Component)]
+struct MultiplierText;
+
+fn multiplier_label(multiplier: &Multiplier) -> String {
+    let total = multiplier.total();
+    if total > 1.0 {
+        format!("x{total:.1}")
+    } else {
+        String::new()
+    }
+}
+
+/// Shows the multiplier of a lone player; with several, each one's sits next to their health.
+fn update_multiplier(
+    mut text: Query<&mut Text, With<MultiplierText>>,
+    party: Res<Party>,
+    players: Query<&Multiplier, With<Player>>,
+) {
+    let label = match (party.players, players.iter().next()) {
+        (1, Some(multiplier)) => multiplier_label(multiplier),
+        _ => String::new(),
+    };
+    for mut text in &mut text {
+        if text.sections[0].value != label {
+            text.sections[0].value.clone_from(&label);
+        }
+    }
+}
+
+#[derive(Component)]
+struct ModeText;
+
+fn update_mode(
+    mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    mut text: Query<&mut Text, With<ModeText>>,
+) {
+    let rules = mode.rules();
+    let clock = |seconds: f32| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let seconds = seconds.max(0.0).ceil() as u32;
+        format!(" {}:{:02}", seconds / 60, seconds % 60)
+    };
+    let label = match (rules.time_limit, rules.survival_points) {
+        (Some(limit), _) => format!("{}{}", mode.label(), clock(limit - difficulty.elapsed)),
+        (None, points) if points > 0 => format!("{}{}", mode.label(), clock(difficulty.elapsed)),
+        _ => mode.label().to_string(),
+    };
+    for mut text in &mut text {
+        if text.sections[0].value != label {
+            text.sections[0].value.clone_from(&label);
+        }
+    }
+}
+
 #[derive(Component)]
 struct EntityCount(u32);
 
+fn entity_count_transform(arena: &Arena) -> GlobalTransform {
+    GlobalTransform::from_translation((arena.bounds().min + 1.0).extend(10.0))
+}
+
+fn place_entity_count(arena: Res<Arena>, mut text: Query<&mut GlobalTransform, With<EntityCount>>) {
+    for mut transform in &mut text {
+        *transform = entity_count_transform(&arena);
+    }
+}
+
 fn update_entity_count(
     mut text: Query<(&mut Text, &mut EntityCount)>,
     mut events: EventReader<SpawnEvent>,
@@ -167,13 +297,354 @@ fn update_entity_count(
     }
 }
 
+#[derive(Component)]
+struct HealthBar;
+
+fn setup_health(mut commands: Commands) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Health"));
+    entity.insert(HealthBar);
+    entity.insert(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.),
+            top: Val::Px(8.),
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.),
+            ..default()
+        },
+        ..default()
+    });
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_health(
+    mut commands: Commands,
+    images: Res<Images>,
+    party: Res<Party>,
+    scores: Res<Scores>,
+    players: Query<(&Player, &Health, &Lives, &Multiplier)>,
+    changed: Query<
+        (),
+        (
+            With<Player>,
+            Or<(Changed<Health>, Changed<Lives>, Changed<Multiplier>)>,
+        ),
+    >,
+    mut removed: RemovedComponents<Player>,
+    bar: Query<Entity, With<HealthBar>>,
+) {
+    let removed = removed.read().count() > 0;
+    let rescored = party.players > 1 && scores.is_changed();
+    if changed.is_empty() && !removed && !rescored {
+        return;
+    }
+    let Ok(bar) = bar.get_single() else {
+        return;
+    };
+
+    let mut players = players.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(player, ..)| player.slot());
+    commands
+        .entity(bar)
+        .despawn_descendants()
+        .with_children(|parent| {
+            for (player, health, lives, multiplier) in players {
+                let slot = player.slot();
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(4.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for _ in 0..health.0 {
+                            parent.spawn(ImageBundle {
+                                style: Style {
+                                    width: Val::Px(24.),
+                                    height: Val::Px(24.),
+                                    ..default()
+                                },
+                                image: UiImage::new(images.player.clone()),
+                                background_color: BackgroundColor(player::tint(slot)),
+                                ..default()
+                            });
+                        }
+                        let mut label = format!("x{}", lives.0);
+                        if party.players > 1 {
+                            label = format!(
+                                "{label}  {} {}",
+                                scores.0[slot % MAX_PLAYERS],
+                                multiplier_label(multiplier)
+                            );
+                        }
+                        parent.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font_size: 24.0,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+#[derive(Component)]
+struct PowerUpTimers;
+
+fn setup_power_ups(mut commands: Commands) {
+    let mut entity = commands.spawn_empty();
+    entity.insert(Name::new("Power-ups"));
+    entity.insert(PowerUpTimers);
+    entity.insert(
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.0,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Right)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.),
+            top: Val::Px(8.),
+            ..default()
+        }),
+    );
+}
+
+fn update_power_ups(
+    mut text: Query<&mut Text, With<PowerUpTimers>>,
+    party: Res<Party>,
+    players: Query<(&Player, Option<&PowerUps>)>,
+) {
+    let Ok(mut text) = text.get_single_mut() else {
+        return;
+    };
+
+    let several = party.players > 1;
+    let mut players = players.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(player, _)| player.slot());
+    text.sections[0].value = players
+        .into_iter()
+        .filter_map(|(player, power_ups)| power_ups.map(|power_ups| (player.slot(), power_ups)))
+        .flat_map(|(slot, power_ups)| {
+            PowerUp::ALL.iter().filter_map(move |power_up| {
+                power_ups.get(power_up).map(|remaining| {
+                    let label = format!("{} {remaining:.1}s", power_up.label());
+                    if several {
+                        format!("P{} {label}", slot + 1)
+                    } else {
+                        label
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
 #[derive(Component)]
 struct GameOverNode;
 
 #[derive(Component)]
 struct TryAgainButton;
 
-fn gameover(mut commands: Commands, ui: Query<Entity, With<StateNode>>) {
+#[derive(Component)]
+struct ModeButton(GameMode);
+
+#[derive(Component)]
+struct PlayersButton(usize);
+
+#[derive(Component)]
+struct StyleButton(PlayStyle);
+
+const SHOWN_SCORES: usize = 5;
+
+fn high_scores(parent: &mut ChildBuilder, scores: &HighScores) {
+    parent.spawn(
+        TextBundle::from_section(
+            "High Scores",
+            TextStyle {
+                font_size: 28.0,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Center),
+    );
+    for (rank, score) in scores.iter().take(SHOWN_SCORES).enumerate() {
+        parent.spawn(
+            TextBundle::from_section(
+                match score.date {
+                    Some(date) => format!(
+                        "{}. {} ({} {date})",
+                        rank + 1,
+                        score.points,
+                        score.mode.label()
+                    ),
+                    None => format!("{}. {} ({})", rank + 1, score.points, score.mode.label()),
+                },
+                TextStyle {
+                    font_size: 20.0,
+                    ..default()
+                },
+            )
+            .with_text_alignment(TextAlignment::Center),
+        );
+    }
+}
+
+fn daily_label(parent: &mut ChildBuilder, challenge: &DailyChallenge) {
+    let Some(date) = challenge.date else {
+        return;
+    };
+    let practice = if challenge.scored { "" } else { " (practice)" };
+    parent.spawn(
+        TextBundle::from_section(
+            format!("Daily challenge {date}{practice}"),
+            TextStyle {
+                font_size: 24.0,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Center),
+    );
+}
+
+fn party_results(parent: &mut ChildBuilder, party: &Party, scores: &Scores, winner: &Winner) {
+    if let Some(slot) = winner.0 {
+        parent.spawn(
+            TextBundle::from_section(
+                format!("{} wins!", player::name(slot)),
+                TextStyle {
+                    font_size: 28.0,
+                    color: player::tint(slot),
+                    ..default()
+                },
+            )
+            .with_text_alignment(TextAlignment::Center),
+        );
+    }
+    for slot in 0..party.players.min(MAX_PLAYERS) {
+        parent.spawn(
+            TextBundle::from_section(
+                format!("{}: {}", player::name(slot), scores.0[slot]),
+                TextStyle {
+                    font_size: 24.0,
+                    color: player::tint(slot),
+                    ..default()
+                },
+            )
+            .with_text_alignment(TextAlignment::Center),
+        );
+    }
+}
+
+fn button_color(interaction: Interaction, selected: bool) -> Color {
+    match interaction {
+        _ if selected => Color::rgb(0.2, 0.2, 0.7),
+        Interaction::Hovered => Color::rgb(0.2, 0.2, 0.44),
+        Interaction::Pressed | Interaction::None => Color::rgb(0.1, 0.1, 0.44),
+    }
+}
+
+fn option_button(parent: &mut ChildBuilder, button: impl Bundle, label: &str) {
+    parent
+        .spawn((
+            button,
+            ButtonBundle {
+                background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.44)),
+                style: Style {
+                    padding: UiRect::all(Val::Px(6.)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 20.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn options_row(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(8.),
+                margin: UiRect::vertical(Val::Px(8.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(children);
+}
+
+fn party_select(parent: &mut ChildBuilder) {
+    options_row(parent, |parent| {
+        for players in 1..=MAX_PLAYERS {
+            option_button(parent, PlayersButton(players), &format!("{players}P"));
+        }
+        for style in PlayStyle::ALL {
+            option_button(parent, StyleButton(style), style.label());
+        }
+    });
+}
+
+fn mode_select(parent: &mut ChildBuilder) {
+    options_row(parent, |parent| {
+        for mode in GameMode::ALL {
+            option_button(parent, ModeButton(mode), mode.label());
+        }
+    });
+}
+
+/// Why the run ended, if there is more to say than "Game Over".
+fn ending(
+    cause: &CauseOfDeath,
+    mode: GameMode,
+    disconnected: Option<&Disconnected>,
+) -> Option<String> {
+    if let Some(disconnected) = disconnected {
+        return Some(format!("Disconnected: {}", disconnected.0));
+    }
+    match &cause.0 {
+        Some((_, Some(attacker))) => Some(format!("Killed by {attacker}")),
+        Some((kind, None)) => Some(format!("Killed by {}", kind.label())),
+        None if mode.rules().time_limit.is_some() => Some("Time's up!".to_string()),
+        None => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gameover(
+    mut commands: Commands,
+    ui: Query<Entity, With<StateNode>>,
+    breakdown: Res<Breakdown>,
+    cause: Res<CauseOfDeath>,
+    mode: Res<GameMode>,
+    scores: Res<HighScores>,
+    challenge: Res<DailyChallenge>,
+    party: Res<Party>,
+    player_scores: Res<Scores>,
+    winner: Res<Winner>,
+    disconnected: Option<Res<Disconnected>>,
+) {
     let Ok(ui) = ui.get_single() else {
         return;
     };
@@ -207,6 +678,43 @@ fn gameover(mut commands: Commands, ui: Query<Entity, With<StateNode>>) {
                     )
                     .with_text_alignment(TextAlignment::Center),
                 );
+                if let Some(text) = ending(&cause, *mode, disconnected.as_deref()) {
+                    parent.spawn(
+                        TextBundle::from_section(
+                            text,
+                            TextStyle {
+                                font_size: 24.0,
+                                color: Color::RED,
+                                ..default()
+                            },
+                        )
+                        .with_text_alignment(TextAlignment::Center),
+                    );
+                }
+                if *mode == GameMode::Daily {
+                    daily_label(parent, &challenge);
+                }
+                if party.players > 1 {
+                    party_results(parent, &party, &player_scores, &winner);
+                }
+                for source in PointSource::ALL {
+                    let Some(amount) = breakdown.get(&source) else {
+                        continue;
+                    };
+                    parent.spawn(
+                        TextBundle::from_section(
+                            format!("{}: {amount}", source.label()),
+                            TextStyle {
+                                font_size: 24.0,
+                                ..default()
+                            },
+                        )
+                        .with_text_alignment(TextAlignment::Center),
+                    );
+                }
+                high_scores(parent, &scores);
+                mode_select(parent);
+                party_select(parent);
                 parent
                     .spawn((
                         TryAgainButton,
@@ -238,30 +746,138 @@ fn gameover(mut commands: Commands, ui: Query<Entity, With<StateNode>>) {
 #[derive(Component)]
 struct StateNode;
 
+#[derive(Component)]
+struct MenuNode;
+
 #[derive(Component)]
 struct MenuText;
 
-fn show_menu(mut commands: Commands, ui: Query<Entity, With<StateNode>>) {
+#[derive(Component)]
+struct ConnectingNode;
+
+fn shake_label(shake: &ShakeSettings) -> String {
+    format!(
+        "\nScreen shake: {} [K]",
+        if shake.enabled { "on" } else { "off" }
+    )
+}
+
+fn difficulty_label(settings: &DifficultySettings, difficulty: &Difficulty) -> String {
+    format!(
+        "\nDifficulty: {} [L], level {:.0}",
+        settings.preset.label(),
+        difficulty.level
+    )
+}
+
+fn show_menu(
+    mut commands: Commands,
+    shake: Res<ShakeSettings>,
+    settings: Res<DifficultySettings>,
+    difficulty: Res<Difficulty>,
+    session: Option<Res<Session>>,
+    ui: Query<Entity, With<StateNode>>,
+) {
+    let Ok(ui) = ui.get_single() else {
+        return;
+    };
+
+    commands.entity(ui).with_children(|parent| {
+        let mut menu = parent.spawn_empty();
+        menu.insert(MenuNode);
+        menu.insert(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        });
+        menu.with_children(|parent| {
+            parent.spawn((
+                MenuText,
+                TextBundle::from_sections([
+                    TextSection::new(
+                        "Paused",
+                        TextStyle {
+                            font_size: 36.0,
+                            ..default()
+                        },
+                    ),
+                    TextSection::new(
+                        shake_label(&shake),
+                        TextStyle {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                    ),
+                    TextSection::new(
+                        difficulty_label(&settings, &difficulty),
+                        TextStyle {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                    ),
+                ])
+                .with_text_alignment(TextAlignment::Center),
+            ));
+            // Picking another mode here starts a fresh run in it, which only this peer would see.
+            if session.is_none() {
+                mode_select(parent);
+            }
+        });
+    });
+}
+
+fn update_menu(
+    shake: Res<ShakeSettings>,
+    settings: Res<DifficultySettings>,
+    difficulty: Res<Difficulty>,
+    mut menu: Query<&mut Text, With<MenuText>>,
+) {
+    for mut text in &mut menu {
+        text.sections[1].value = shake_label(&shake);
+        text.sections[2].value = difficulty_label(&settings, &difficulty);
+    }
+}
+
+fn show_connecting(mut commands: Commands, ui: Query<Entity, With<StateNode>>) {
     let Ok(ui) = ui.get_single() else {
         return;
     };
 
     commands.entity(ui).with_children(|parent| {
         parent.spawn((
-            MenuText,
-            TextBundle::from_section(
-                "Paused",
-                TextStyle {
-                    font_size: 36.0,
-                    ..default()
-                },
-            )
+            ConnectingNode,
+            TextBundle::from_sections([
+                TextSection::new(
+                    "Connecting",
+                    TextStyle {
+                        font_size: 36.0,
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    "\nWaiting for the other player...",
+                    TextStyle {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                ),
+            ])
             .with_text_alignment(TextAlignment::Center),
         ));
     });
 }
 
-fn hide_menu(mut commands: Commands, ui: Query<Entity, With<MenuText>>) {
+fn hide_connecting(mut commands: Commands, ui: Query<Entity, With<ConnectingNode>>) {
+    for node in &ui {
+        commands.entity(node).despawn_recursive();
+    }
+}
+
+fn hide_menu(mut commands: Commands, ui: Query<Entity, With<MenuNode>>) {
     let Ok(menu) = ui.get_single() else {
         return;
     };
@@ -271,7 +887,10 @@ fn hide_menu(mut commands: Commands, ui: Query<Entity, With<MenuText>>) {
 
 #[allow(clippy::type_complexity)]
 fn try_again_button(
-    mut button: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    mut button: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<TryAgainButton>),
+    >,
     mut state: ResMut<NextState<GameState>>,
 ) {
     let Ok((interaction, mut background)) = button.get_single_mut() else {
@@ -292,6 +911,53 @@ fn try_again_button(
     }
 }
 
+fn mode_buttons(
+    mut commands: Commands,
+    mut mode: ResMut<GameMode>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut buttons: Query<(&Interaction, &ModeButton, &mut BackgroundColor)>,
+) {
+    for (interaction, ModeButton(button), _) in &buttons {
+        if *interaction == Interaction::Pressed && *mode != *button {
+            *mode = *button;
+            if *state.get() == GameState::Paused {
+                commands.insert_resource(Restart);
+                next_state.set(GameState::GameOver);
+            }
+        }
+    }
+
+    for (interaction, ModeButton(button), mut background) in &mut buttons {
+        background.0 = button_color(*interaction, *button == *mode);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn party_buttons(
+    mut party: ResMut<Party>,
+    mut players: Query<(&Interaction, &PlayersButton, &mut BackgroundColor), Without<StyleButton>>,
+    mut styles: Query<(&Interaction, &StyleButton, &mut BackgroundColor), Without<PlayersButton>>,
+) {
+    for (interaction, PlayersButton(count), _) in &players {
+        if *interaction == Interaction::Pressed && party.players != *count {
+            party.players = *count;
+        }
+    }
+    for (interaction, StyleButton(style), _) in &styles {
+        if *interaction == Interaction::Pressed && party.style != *style {
+            party.style = *style;
+        }
+    }
+
+    for (interaction, PlayersButton(count), mut background) in &mut players {
+        background.0 = button_color(*interaction, *count == party.players);
+    }
+    for (interaction, StyleButton(style), mut background) in &mut styles {
+        background.0 = button_color(*interaction, *style == party.style);
+    }
+}
+
 fn reset(
     mut commands: Commands,
     ui: Query<Entity, With<GameOverNode>>,
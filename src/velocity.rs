@@ -17,7 +17,7 @@ impl bevy::prelude::Plugin for Plugin {
 #[cfg_attr(feature = "inspector", derive(Reflect))]
 pub struct Velocity(pub Vec2);
 
-fn update(mut boids: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
+pub fn update(mut boids: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
     for (mut transform, vel) in &mut boids {
         transform.translation += vel.extend(0.0) * time.delta_seconds();
     }